@@ -6,8 +6,9 @@ fn main() {
     let parent = win.parent().unwrap();
 
     let (px, py, pw, ph) = parent.visual_geometry().unwrap();
-    let (x, y, w, h) = win.geometry().unwrap();
-    let (vx, vy, vw, vh) = win.visual_geometry().unwrap();
+    let (raw, visual) = win.geometries().unwrap();
+    let (x, y, w, h) = raw.into();
+    let (vx, vy, vw, vh) = visual.into();
     let b = win.borders();
     let g = win.gtk_borders();
 
@@ -9,6 +9,7 @@ fn main() {
     println!("-----------------------------------------------------------------------");
     println!("Window Manager: {}", wm.name);
     println!("Compositing:    {}", wm.compositing);
+    println!("Compositor:     {}", wm.compositor.unwrap_or_default());
     println!("Root Window:    {}", wm.root_win_id);
     println!("Work area:      {}x{}", wm.work_area.0, wm.work_area.1);
     println!("Screen Size:    {}x{}", wm.screen_size.0, wm.screen_size.1);
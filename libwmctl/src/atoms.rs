@@ -3,96 +3,126 @@ use x11rb::atom_manager;
 // This is a collection of the atoms we will need. The macro invocation below will look up the atoms
 // by name and store their values in this struct for later use at runtime. In order for this to work,
 // we need to invoke this as an initializer in the main functionw with `AtomCollection::new(conn)?;
+// Wrapping the invocation allows for also generating the list of atom names from the same source.
+macro_rules! atoms {
+    ($($name:ident,)*) => {
+        atom_manager! {
+            pub AtomCollection: AtomCollectionCookie {
+                $($name,)*
+            }
+        }
 
-atom_manager! {
-    pub AtomCollection: AtomCollectionCookie {
-        // GNOME custom hints
-        _GTK_FRAME_EXTENTS,
+        /// Names of all the atoms in the `AtomCollection`
+        pub const ATOM_NAMES: &[&str] = &[$(stringify!($name),)*];
 
-        // Standard Extended Window Manager Hints
-        _NET_ACTIVE_WINDOW,
-        _NET_CLIENT_LIST,
-        _NET_CLIENT_LIST_STACKING,
-        _NET_CLOSE_WINDOW,
-        _NET_CURRENT_DESKTOP,
-        _NET_DESKTOP_GEOMETRY,
-        _NET_DESKTOP_LAYOUT,
-        _NET_DESKTOP_NAMES,
-        _NET_DESKTOP_VIEWPORT,
-        _NET_FRAME_EXTENTS,
-        _NET_MOVERESIZE_WINDOW,
-        _NET_NUMBER_OF_DESKTOPS,
-        _NET_REQUEST_FRAME_EXTENTS,
-        _NET_SHOWING_DESKTOP,
-        _NET_SUPPORTED,
-        _NET_SUPPORTING_WM_CHECK,
-        _NET_SYSTEM_TRAY_OPCODE,
-        _NET_WORKAREA,
-        _NET_WM_ACTION_ABOVE,
-        _NET_WM_ACTION_BELOW,
-        _NET_WM_ACTION_CHANGE_DESKTOP,
-        _NET_WM_ACTION_CLOSE,
-        _NET_WM_ACTION_FULLSCREEN,
-        _NET_WM_ACTION_MAXIMIZE_HORZ,
-        _NET_WM_ACTION_MAXIMIZE_VERT,
-        _NET_WM_ACTION_MINIMIZE,
-        _NET_WM_ACTION_MOVE,
-        _NET_WM_ACTION_RESIZE,
-        _NET_WM_ACTION_SHADE,
-        _NET_WM_ACTION_STICK,
-        _NET_WM_ALLOWED_ACTIONS,
-        _NET_WM_BYPASS_COMPOSITOR,
-        _NET_WM_CONTEXT_HELP,
-        _NET_WM_DESKTOP,
-        _NET_WM_FULLSCREEN_MONITORS,
-        _NET_WM_HANDLED_ICONS,
-        _NET_WM_ICON,
-        _NET_WM_ICON_GEOMETRY,
-        _NET_WM_ICON_NAME,
-        _NET_WM_MOVERESIZE,
-        _NET_WM_NAME,
-        _NET_WM_OPAQUE_REGION,
-        _NET_WM_PID,
-        _NET_WM_PING,
-        _NET_WM_WINDOW_OPACITY,
-        _NET_WM_WINDOW_OPACITY_LOCKED,
-        _NET_WM_STATE,
-        _NET_WM_STATE_ABOVE,
-        _NET_WM_STATE_BELOW,
-        _NET_WM_STATE_DEMANDS_ATTENTION,
-        _NET_WM_STATE_FOCUSED,
-        _NET_WM_STATE_FULLSCREEN,
-        _NET_WM_STATE_HIDDEN,
-        _NET_WM_STATE_MAXIMIZED_VERT,
-        _NET_WM_STATE_MAXIMIZED_HORZ,
-        _NET_WM_STATE_MODAL,
-        _NET_WM_STATE_SHADED,
-        _NET_WM_STATE_SKIP_PAGER,
-        _NET_WM_STATE_SKIP_TASKBAR,
-        _NET_WM_STATE_STICKY,
-        _NET_WM_STRUT,
-        _NET_WM_STRUT_PARTIAL,
-        _NET_WM_SYNC_REQUEST,
-        _NET_WM_SYNC_REQUEST_COUNTER,
-        _NET_WM_USER_TIME,
-        _NET_WM_USER_TIME_WINDOW,
-        _NET_WM_VISIBLE_NAME,
-        _NET_WM_VISIBLE_ICON_NAME,
-        _NET_WM_WINDOW_TYPE,
-        _NET_WM_WINDOW_TYPE_COMBO,
-        _NET_WM_WINDOW_TYPE_DESKTOP,
-        _NET_WM_WINDOW_TYPE_DIALOG,
-        _NET_WM_WINDOW_TYPE_DND,
-        _NET_WM_WINDOW_TYPE_DOCK,
-        _NET_WM_WINDOW_TYPE_DROPDOWN_MENU,
-        _NET_WM_WINDOW_TYPE_MENU,
-        _NET_WM_WINDOW_TYPE_NORMAL,
-        _NET_WM_WINDOW_TYPE_NOTIFICATION,
-        _NET_WM_WINDOW_TYPE_POPUP_MENU,
-        _NET_WM_WINDOW_TYPE_SPLASH,
-        _NET_WM_WINDOW_TYPE_TOOLBAR,
-        _NET_WM_WINDOW_TYPE_TOOLTIP,
-        _NET_WM_WINDOW_TYPE_UTILITY,
-        UTF8_STRING,
-    }
+        #[cfg(test)]
+        impl AtomCollection {
+            /// Create the collection with unique made up atom ids for testing without a connection
+            pub(crate) fn test() -> Self {
+                let mut id = 0;
+                Self { $($name: { id += 1; id },)* }
+            }
+        }
+    };
+}
+
+atoms! {
+    // GNOME custom hints
+    _GTK_FRAME_EXTENTS,
+    _GTK_SHOW_WINDOW_MENU,
+
+    // KDE custom hints
+    _KDE_NET_WM_FRAME_STRUT,
+
+    // Motif hints
+    _MOTIF_WM_HINTS,
+
+    // wmctl's own properties
+    _WMCTL_HISTORY,
+
+    // Standard Extended Window Manager Hints
+    _NET_ACTIVE_WINDOW,
+    _NET_CLIENT_LIST,
+    _NET_CLIENT_LIST_STACKING,
+    _NET_CLOSE_WINDOW,
+    _NET_CURRENT_DESKTOP,
+    _NET_DESKTOP_GEOMETRY,
+    _NET_DESKTOP_LAYOUT,
+    _NET_DESKTOP_NAMES,
+    _NET_DESKTOP_VIEWPORT,
+    _NET_FRAME_EXTENTS,
+    _NET_MOVERESIZE_WINDOW,
+    _NET_NUMBER_OF_DESKTOPS,
+    _NET_REQUEST_FRAME_EXTENTS,
+    _NET_SHOWING_DESKTOP,
+    _NET_SUPPORTED,
+    _NET_SUPPORTING_WM_CHECK,
+    _NET_SYSTEM_TRAY_OPCODE,
+    _NET_WORKAREA,
+    _NET_WM_ACTION_ABOVE,
+    _NET_WM_ACTION_BELOW,
+    _NET_WM_ACTION_CHANGE_DESKTOP,
+    _NET_WM_ACTION_CLOSE,
+    _NET_WM_ACTION_FULLSCREEN,
+    _NET_WM_ACTION_MAXIMIZE_HORZ,
+    _NET_WM_ACTION_MAXIMIZE_VERT,
+    _NET_WM_ACTION_MINIMIZE,
+    _NET_WM_ACTION_MOVE,
+    _NET_WM_ACTION_RESIZE,
+    _NET_WM_ACTION_SHADE,
+    _NET_WM_ACTION_STICK,
+    _NET_WM_ALLOWED_ACTIONS,
+    _NET_WM_BYPASS_COMPOSITOR,
+    _NET_WM_CONTEXT_HELP,
+    _NET_WM_DESKTOP,
+    _NET_WM_FULLSCREEN_MONITORS,
+    _NET_WM_HANDLED_ICONS,
+    _NET_WM_ICON,
+    _NET_WM_ICON_GEOMETRY,
+    _NET_WM_ICON_NAME,
+    _NET_WM_MOVERESIZE,
+    _NET_WM_NAME,
+    _NET_WM_OPAQUE_REGION,
+    _NET_WM_PID,
+    _NET_WM_PING,
+    _NET_WM_WINDOW_OPACITY,
+    _NET_WM_WINDOW_OPACITY_LOCKED,
+    _NET_WM_STATE,
+    _NET_WM_STATE_ABOVE,
+    _NET_WM_STATE_BELOW,
+    _NET_WM_STATE_DEMANDS_ATTENTION,
+    _NET_WM_STATE_FOCUSED,
+    _NET_WM_STATE_FULLSCREEN,
+    _NET_WM_STATE_HIDDEN,
+    _NET_WM_STATE_MAXIMIZED_VERT,
+    _NET_WM_STATE_MAXIMIZED_HORZ,
+    _NET_WM_STATE_MODAL,
+    _NET_WM_STATE_SHADED,
+    _NET_WM_STATE_SKIP_PAGER,
+    _NET_WM_STATE_SKIP_TASKBAR,
+    _NET_WM_STATE_STICKY,
+    _NET_WM_STRUT,
+    _NET_WM_STRUT_PARTIAL,
+    _NET_WM_SYNC_REQUEST,
+    _NET_WM_SYNC_REQUEST_COUNTER,
+    _NET_WM_USER_TIME,
+    _NET_WM_USER_TIME_WINDOW,
+    _NET_WM_VISIBLE_NAME,
+    _NET_WM_VISIBLE_ICON_NAME,
+    _NET_WM_WINDOW_TYPE,
+    _NET_WM_WINDOW_TYPE_COMBO,
+    _NET_WM_WINDOW_TYPE_DESKTOP,
+    _NET_WM_WINDOW_TYPE_DIALOG,
+    _NET_WM_WINDOW_TYPE_DND,
+    _NET_WM_WINDOW_TYPE_DOCK,
+    _NET_WM_WINDOW_TYPE_DROPDOWN_MENU,
+    _NET_WM_WINDOW_TYPE_MENU,
+    _NET_WM_WINDOW_TYPE_NORMAL,
+    _NET_WM_WINDOW_TYPE_NOTIFICATION,
+    _NET_WM_WINDOW_TYPE_POPUP_MENU,
+    _NET_WM_WINDOW_TYPE_SPLASH,
+    _NET_WM_WINDOW_TYPE_TOOLBAR,
+    _NET_WM_WINDOW_TYPE_TOOLTIP,
+    _NET_WM_WINDOW_TYPE_UTILITY,
+    UTF8_STRING,
 }
@@ -7,35 +7,58 @@ pub type WmCtlResult<T> = std::result::Result<T, ErrorWrapper>;
 /// WmCtlError defines all the internal errors that `libwmctl` might return
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum WmCtlError {
+    DesktopNotFound(u32),
     DesktopWinNotFound,
     InvalidAtom(String),
+    InvalidDesktopCount(u32),
+    InvalidFraction(String),
+    InvalidGeometry(String),
+    InvalidMargin(u32),
     InvalidWinGravity(u32),
     InvalidWinPosition(String),
     InvalidWinShape(String),
     InvalidWinClass(String),
     InvalidWinMap(u32),
     InvalidWinState(u32),
+    InvalidWinStateName(String),
     InvalidWinType(u32),
+    MonitorNotFound(String, String),
+    NotResizable(u32),
+    NotViewable(u32),
     PropertyNotFound(String),
     TaskbarNotFound,
     TaskbarReservationNotFound,
+    Unsupported(String),
 }
 impl std::error::Error for WmCtlError {}
 impl fmt::Display for WmCtlError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            WmCtlError::DesktopNotFound(ref err) => write!(f, "desktop {} was not found", err),
             WmCtlError::DesktopWinNotFound => write!(f, "desktop window was not found"),
             WmCtlError::InvalidAtom(ref err) => write!(f, "invalid atom was given: {}", err),
+            WmCtlError::InvalidDesktopCount(ref err) => write!(f, "invalid desktop count was given: {}", err),
+            WmCtlError::InvalidFraction(ref err) => write!(f, "invalid fraction was given: {}", err),
+            WmCtlError::InvalidGeometry(ref err) => write!(f, "invalid geometry was given: {}", err),
+            WmCtlError::InvalidMargin(ref err) => write!(f, "invalid margin was given: {}", err),
             WmCtlError::InvalidWinGravity(ref err) => write!(f, "invalid gravity was given: {}", err),
             WmCtlError::InvalidWinPosition(ref err) => write!(f, "invalid position was given: {}", err),
             WmCtlError::InvalidWinShape(ref err) => write!(f, "invalid shape was given: {}", err),
             WmCtlError::InvalidWinClass(ref err) => write!(f, "invalid class was given: {}", err),
             WmCtlError::InvalidWinMap(ref err) => write!(f, "invalid map was given: {}", err),
             WmCtlError::InvalidWinState(ref err) => write!(f, "invalid state was given: {}", err),
+            WmCtlError::InvalidWinStateName(ref err) => write!(f, "invalid state name was given: {}", err),
             WmCtlError::InvalidWinType(ref err) => write!(f, "invalid type was given: {}", err),
+            WmCtlError::MonitorNotFound(ref name, ref valid) => match valid.is_empty() {
+                true => write!(f, "monitor {} was not found", name),
+                false => write!(f, "monitor {} was not found, valid monitors are: {}", name, valid),
+            },
+            WmCtlError::NotResizable(ref err) => write!(f, "window {} is not resizable", err),
+            WmCtlError::NotViewable(ref err) => write!(f, "window {} is not viewable or is off screen", err),
             WmCtlError::PropertyNotFound(ref err) => write!(f, "property {} was not found", err),
             WmCtlError::TaskbarNotFound => write!(f, "taskbar not found"),
             WmCtlError::TaskbarReservationNotFound => write!(f, "taskbar reservation not found"),
+            WmCtlError::Unsupported(ref err) => write!(f, "window manager doesn't support {}", err),
         }
     }
 }
@@ -70,6 +93,15 @@ impl ErrorWrapper {
         self.as_mut().downcast_mut::<T>()
     }
 
+    /// Check if the error was caused by a broken connection to the X server e.g. after the server
+    /// restarted in which case `libwmctl::reconnect` can be used to recover.
+    pub fn is_connection_error(&self) -> bool {
+        matches!(
+            self,
+            ErrorWrapper::Connection(_) | ErrorWrapper::Reply(x11rb::errors::ReplyError::ConnectionError(_))
+        )
+    }
+
     /// Implemented directly on the `Error` type to reduce casting required
     /// which allows for using as_ref to get the correct pass through.
     pub fn source(&self) -> Option<&(dyn StdError + 'static)> {
@@ -16,13 +16,16 @@
 mod atoms;
 mod error;
 mod model;
+mod watch;
 mod window;
 mod winmgr;
 pub use atoms::*;
 pub use error::*;
 pub use model::*;
+use tracing::debug;
+pub use watch::*;
 pub use window::Window;
-use winmgr::WinMgr;
+pub use winmgr::WinMgr;
 
 /// All essential symbols in a simple consumable form
 ///
@@ -36,15 +39,69 @@ pub mod prelude {
 }
 
 /// Singleton providing a single instance of WmCtl shared across the application. Using RwLock here
-/// since changing the instance won't ever happen and RwLock allows for multiple readers making this
-/// as efficient as possible.
-use std::sync::{OnceLock, RwLock};
+/// since changing the instance only happens on reconnect and RwLock allows for multiple readers
+/// making this as efficient as possible.
+use std::sync::{Arc, OnceLock, RwLock};
 #[allow(non_snake_case)]
 fn WM() -> &'static RwLock<WinMgr> {
     static INIT: OnceLock<RwLock<WinMgr>> = OnceLock::new();
     INIT.get_or_init(|| RwLock::new(WinMgr::connect().unwrap()))
 }
 
+/// Replace the global connection with a new connection to the X server e.g. after the X server
+/// restarted and calls started failing with `ErrorWrapper::is_connection_error`. Settings such as
+/// the source indication and ignored classes are carried over to the new connection.
+/// * Windows created with `Window::with_winmgr` are unaffected as they use their own connection
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// if let Err(err) = libwmctl::active_desktop() {
+///     if err.is_connection_error() {
+///         libwmctl::reconnect().unwrap();
+///     }
+/// }
+/// ```
+pub fn reconnect() -> WmCtlResult<()> {
+    let mut wm = WinMgr::connect()?;
+    let mut global = WM().write().unwrap();
+    wm.source_indication = global.source_indication;
+    wm.ignored_classes = std::mem::take(&mut global.ignored_classes);
+    *global = wm;
+    Ok(())
+}
+
+/// Watch the window manager for changes e.g. the active window or current desktop changing. The
+/// returned iterator blocks until the next event arrives and uses its own connection.
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// for event in libwmctl::watch().unwrap() {
+///     println!("{}", event.unwrap());
+/// }
+/// ```
+pub fn watch() -> WmCtlResult<Watcher> {
+    Watcher::new()
+}
+
+/// Watch the window manager for changes as an async stream for use in async event loops
+/// * Requires the `async` feature
+///
+/// ### Examples
+/// ```ignore
+/// use futures::StreamExt;
+/// use libwmctl::prelude::*;
+/// let mut events = libwmctl::watch_stream().unwrap();
+/// while let Some(event) = events.next().await {
+///     println!("{}", event.unwrap());
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub fn watch_stream() -> WmCtlResult<WatchStream> {
+    WatchStream::new()
+}
+
 /// Get window manager informational properties
 ///
 /// ### Examples
@@ -56,7 +113,134 @@ pub fn info() -> WmCtlResult<Info> {
     Ok(WM().read().unwrap().info()?)
 }
 
+/// Get the active desktop
+/// * Returns non zero based desktop number to align with `Window::desktop`
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let desktop = libwmctl::active_desktop().unwrap();
+/// ```
+pub fn active_desktop() -> WmCtlResult<u32> {
+    WM().read().unwrap().current_desktop()
+}
+
+/// Get the number of desktops
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let count = libwmctl::desktops().unwrap();
+/// ```
+pub fn desktops() -> WmCtlResult<u32> {
+    WM().read().unwrap().desktops()
+}
+
+/// Switch the current desktop to the given desktop
+///
+/// ### Arguments
+/// * `desktop` - non zero based desktop number to switch to
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// libwmctl::switch_desktop(2).unwrap();
+/// ```
+pub fn switch_desktop(desktop: u32) -> WmCtlResult<()> {
+    let wm = WM().read().unwrap();
+    if desktop == 0 || desktop > wm.desktops()? {
+        return Err(WmCtlError::DesktopNotFound(desktop).into());
+    }
+    wm.switch_desktop(desktop - 1)
+}
+
+/// Get the names of the desktops in desktop order
+/// * Returns an empty list if the window manager doesn't name its desktops
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let names = libwmctl::desktop_names().unwrap();
+/// ```
+pub fn desktop_names() -> WmCtlResult<Vec<String>> {
+    WM().read().unwrap().desktop_names()
+}
+
+/// Set the number of desktops
+/// * Returns an `Unsupported` error if the window manager doesn't support the feature
+///
+/// ### Arguments
+/// * `count` - number of desktops to have, must be at least 1
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// libwmctl::set_desktop_count(4).unwrap();
+/// ```
+pub fn set_desktop_count(count: u32) -> WmCtlResult<()> {
+    WM().read().unwrap().set_desktops(count)
+}
+
+/// Determine if the window manager is showing the desktop i.e. all windows are hidden
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let showing = libwmctl::showing_desktop().unwrap();
+/// ```
+pub fn showing_desktop() -> WmCtlResult<bool> {
+    WM().read().unwrap().showing_desktop()
+}
+
+/// Hide all windows to show the desktop or restore them again
+/// * Returns an `Unsupported` error if the window manager doesn't support the feature
+///
+/// ### Arguments
+/// * `enable` - true to show the desktop, false to restore the windows
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// libwmctl::set_showing_desktop(true).unwrap();
+/// ```
+pub fn set_showing_desktop(enable: bool) -> WmCtlResult<()> {
+    WM().read().unwrap().set_showing_desktop(enable)
+}
+
+/// Set the source indication sent with client messages to the window manager
+/// * Defaults to `SOURCE_INDICATION_PAGER` as wmctl acts on behalf of the user like a pager would
+/// * Some window managers ignore messages using the legacy source indication
+///
+/// ### Arguments
+/// * `source` - source indication to use e.g. `SOURCE_INDICATION_APPLICATION`
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// libwmctl::set_source_indication(SOURCE_INDICATION_APPLICATION);
+/// ```
+pub fn set_source_indication(source: SourceIndication) {
+    WM().write().unwrap().source_indication = source;
+}
+
+/// Set the window classes that bulk operations e.g. pack() should leave alone such as conky or
+/// panels. Classes are compared ignoring case and replace any previously set classes.
+///
+/// ### Arguments
+/// * `classes` - classes of the windows to ignore
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// libwmctl::set_ignored_classes(&["conky", "plank"]);
+/// ```
+pub fn set_ignored_classes(classes: &[&str]) {
+    WM().write().unwrap().ignored_classes = classes.iter().map(|x| x.to_lowercase()).collect();
+}
+
 /// Get the active window
+/// * The active window is the one the window manager advertises via _NET_ACTIVE_WINDOW which some
+///   window managers update lazily so it may lag behind the window with input focus see focused()
 ///
 /// ### Examples
 /// ```ignore
@@ -67,6 +251,27 @@ pub fn active() -> Window {
     Window::from(None)
 }
 
+/// Get the focused window i.e. the managed window that has input focus
+/// * Uses the window advertising the _NET_WM_STATE_FOCUSED state falling back on the active window
+///   if the window manager doesn't support the state
+/// * Prefer this over active() when acting on the window the user is typing into
+/// * Returns None if there is neither a focused nor an active window
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let win = libwmctl::focused().unwrap();
+/// ```
+pub fn focused() -> WmCtlResult<Option<Window>> {
+    let wm = WM().read().unwrap();
+    for id in wm.windows(false)? {
+        if wm.window_state(id).is_ok_and(|x| x.contains(&State::Focused)) {
+            return Ok(Some(Window::new(id)));
+        }
+    }
+    Ok(wm.active_window().ok().filter(|&id| id != 0).map(Window::new))
+}
+
 /// Get the window by id
 ///
 /// ### Arguments
@@ -100,6 +305,413 @@ pub fn windows(hidden: bool) -> WmCtlResult<Vec<Window>> {
         .collect::<WmCtlResult<Vec<Window>>>()
 }
 
+/// Get all the windows the window manager is managing paired with their visual geometry
+/// * Geometry for all windows is fetched in a single batch of requests making this the efficient
+///   starting point for layout algorithms rather than calling visual_geometry() per window
+/// * Windows whose geometry can't be read e.g. having been closed are skipped
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// for (win, g) in libwmctl::windows_with_geometry().unwrap() {
+///     println!("{}: {}x{}", win.id, g.w, g.h);
+/// }
+/// ```
+pub fn windows_with_geometry() -> WmCtlResult<Vec<(Window, Geometry)>> {
+    let wm = WM().read().unwrap();
+    let ids = wm.windows(false)?;
+    Ok(wm.windows_visual_geometry(&ids)?.into_iter().map(|(id, g)| (Window::new(id), g)).collect())
+}
+
+/// Get the managed normal windows sorted spatially by their visual geometry
+/// * Sort key is the top edge `y`, then the left edge `x`, then the window id as a tie breaker
+/// * Windows whose type or geometry can't be read are skipped
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let windows = libwmctl::windows_sorted().unwrap();
+/// ```
+pub fn windows_sorted() -> WmCtlResult<Vec<Window>> {
+    let mut windows = windows_with_geometry()?
+        .into_iter()
+        .filter(|(x, _)| x.kind().is_ok_and(|kind| kind == Kind::Normal))
+        .map(|(x, g)| ((g.y, g.x, x.id), x))
+        .collect::<Vec<_>>();
+    windows.sort_by_key(|(key, _)| *key);
+    Ok(windows.into_iter().map(|(_, x)| x).collect())
+}
+
+/// Get the managed windows grouped by class sorted by class name e.g. for a dock or taskbar
+/// * Windows without a class are grouped under `unknown`
+/// * Windows keep the window manager's client list order within their group
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// for (class, windows) in libwmctl::windows_grouped().unwrap() {
+///     println!("{}: {}", class, windows.len());
+/// }
+/// ```
+pub fn windows_grouped() -> WmCtlResult<Vec<(String, Vec<Window>)>> {
+    let mut groups = std::collections::BTreeMap::<String, Vec<Window>>::new();
+    for win in windows(false)? {
+        let class = win.class().ok().filter(|x| !x.is_empty()).unwrap_or("unknown".to_owned());
+        groups.entry(class).or_default().push(win);
+    }
+    Ok(groups.into_iter().collect())
+}
+
+/// Activate the next visible normal window in most recently used order i.e. Alt-Tab
+/// * The active window history is kept on the root window such that it is shared by every process
+///   using the library e.g. each `wmctl` invocation
+/// * The history only covers windows seen active by the library i.e. the active window when
+///   cycling and active window changes while a watch e.g. the `wmctl daemon` is running
+/// * Falls back on the stacking order for windows not in the history
+///
+/// ### Arguments
+/// * `reverse` - activate the least recently used window instead of the previous one
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// libwmctl::cycle_windows(false).unwrap();
+/// ```
+pub fn cycle_windows(reverse: bool) -> WmCtlResult<()> {
+    let (active, ids) = {
+        let wm = WM().read().unwrap();
+        let active = wm.active_window().unwrap_or(0);
+        wm.record_active(active);
+        (active, wm.windows_mru()?)
+    };
+
+    // Candidates are the other windows in most recently used order
+    let windows = ids
+        .into_iter()
+        .filter(|&x| x != active)
+        .map(Window::new)
+        .filter(|x| x.kind().is_ok_and(|kind| kind == Kind::Normal) && x.is_visible().unwrap_or(false))
+        .collect::<Vec<_>>();
+    let next = if reverse { windows.last() } else { windows.first() };
+    match next {
+        Some(win) => win.activate(),
+        None => Ok(()),
+    }
+}
+
+/// Swap the geometry and desktop of the two windows e.g. to exchange the positions of two tiled
+/// windows. Each window is clamped into its destination's work area.
+///
+/// ### Arguments
+/// * `a` - first window to swap
+/// * `b` - second window to swap
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// libwmctl::swap(&window(1234), &window(5678)).unwrap();
+/// ```
+pub fn swap(a: &Window, b: &Window) -> WmCtlResult<()> {
+    a.swap(b)
+}
+
+// Get the windows bulk operations should arrange i.e. windows_sorted() minus the ignored classes
+fn arrangeable_windows() -> WmCtlResult<Vec<Window>> {
+    let ignored = WM().read().unwrap().ignored_classes.clone();
+    Ok(windows_sorted()?
+        .into_iter()
+        .filter(|x| ignored.is_empty() || !ignored.contains(&x.class().unwrap_or_default().to_lowercase()))
+        .collect())
+}
+
+/// Pack the visible normal windows toward the top left of their monitor's work area removing the
+/// gaps between them. Windows are placed in rows in spatial order keeping their sizes unchanged.
+/// * Maximized and fullscreen windows are left alone
+/// * Windows of the classes given to set_ignored_classes() are left alone
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// libwmctl::pack().unwrap();
+/// ```
+pub fn pack() -> WmCtlResult<()> {
+    // Group the windows by the work area they are in keeping the spatial order
+    let mut groups: Vec<(Geometry, Vec<(Window, Geometry)>)> = vec![];
+    for win in arrangeable_windows()? {
+        let state = win.state().unwrap_or_default();
+        if !win.is_visible().unwrap_or(false)
+            || state.contains(&State::Fullscreen)
+            || (state.contains(&State::MaxVert) && state.contains(&State::MaxHorz))
+        {
+            continue;
+        }
+        let (Ok(area), Ok(g)) = (win.work_area(), win.visual_geometry()) else {
+            continue;
+        };
+        match groups.iter_mut().find(|(x, _)| *x == area) {
+            Some((_, group)) => group.push((win, g.into())),
+            None => groups.push((area, vec![(win, g.into())])),
+        }
+    }
+
+    // Pack each work area moving only the windows whose position changed
+    for (area, group) in groups {
+        let sizes = group.iter().map(|(_, g)| (g.w, g.h)).collect::<Vec<_>>();
+        for ((win, g), (x, y)) in group.iter().zip(window::shelf_pack(&area, &sizes)) {
+            if (x, y) != (g.x, g.y) {
+                win.move_resize_visual(x, y, g.w, g.h)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Move every window on the given monitor to another monitor e.g. before unplugging the monitor.
+/// Windows keep their position relative to the monitor's work area and are clamped into the
+/// destination's work area.
+/// * Sticky and dock windows are left alone
+/// * Maximized windows are re-maximized on the destination monitor
+/// * Windows that fail to be read or moved e.g. having been closed are skipped
+///
+/// ### Arguments
+/// * `name` - name of the monitor to move windows off of e.g. HDMI-1
+/// * `to` - name of the monitor to move the windows to
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// libwmctl::evacuate_monitor("HDMI-1", "eDP-1").unwrap();
+/// ```
+pub fn evacuate_monitor(name: &str, to: &str) -> WmCtlResult<()> {
+    let (from, to) = {
+        let wm = WM().read().unwrap();
+        (wm.monitor(name)?, wm.monitor(to)?)
+    };
+    for win in windows(false)? {
+        let state = win.state().unwrap_or_default();
+        if state.contains(&State::Sticky) || win.kind().is_ok_and(|x| x == Kind::Dock) {
+            continue;
+        }
+        // Skip windows that fail e.g. closed mid loop so they can't strand the rest
+        let monitor = WM().read().unwrap().window_monitor(win.id);
+        if !monitor.is_ok_and(|x| x.is_some_and(|x| x.name == from.name)) {
+            continue;
+        }
+        let Ok(g) = win.visual_geometry() else {
+            continue;
+        };
+        if let Err(err) = evacuate_window(&win, &g.into(), &from, &to) {
+            debug!("evacuate_monitor: failed to move window {}: {}", win.id, err);
+        }
+    }
+    Ok(())
+}
+
+// Move the window with the given visual geometry from the one monitor's work area to the other's
+fn evacuate_window(win: &Window, g: &Geometry, from: &Monitor, to: &Monitor) -> WmCtlResult<()> {
+    // Window managers won't move maximized windows so unmaximize them first
+    let maximized = win.maximized();
+    if maximized {
+        win.unmaximize()?;
+    }
+    let g = window::relocate(g, &from.work_area, &to.work_area);
+    win.move_resize_visual(g.x, g.y, g.w, g.h)?;
+    if maximized {
+        win.maximize()?;
+    }
+    Ok(())
+}
+
+/// Get the monitors as reported by the RandR extension
+/// * Returns an empty list if the RandR extension isn't available
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let monitors = libwmctl::monitors().unwrap();
+/// ```
+pub fn monitors() -> WmCtlResult<Vec<Monitor>> {
+    WM().read().unwrap().monitors()
+}
+
+/// Get the monitor by name
+/// * Returns a `MonitorNotFound` error listing the valid monitor names if the name doesn't match
+///
+/// ### Arguments
+/// * `name` - name of the monitor e.g. HDMI-1
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let monitor = libwmctl::monitor("HDMI-1").unwrap();
+/// ```
+pub fn monitor(name: &str) -> WmCtlResult<Monitor> {
+    WM().read().unwrap().monitor(name)
+}
+
+/// Get the managed windows whose center lies on the given monitor e.g. to debug per display layouts
+/// * Windows whose geometry can't be read are skipped
+/// * Returns a `MonitorNotFound` error listing the valid monitor names if the name doesn't match
+///
+/// ### Arguments
+/// * `name` - name of the monitor e.g. HDMI-1
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let windows = libwmctl::windows_on_monitor("HDMI-1").unwrap();
+/// ```
+pub fn windows_on_monitor(name: &str) -> WmCtlResult<Vec<Window>> {
+    let monitor = monitor(name)?;
+    Ok(windows_with_geometry()?
+        .into_iter()
+        .filter(|(_, g)| {
+            let (cx, cy) = g.center();
+            monitor.geometry.contains(cx, cy)
+        })
+        .map(|(x, _)| x)
+        .collect())
+}
+
+/// Parse the named window state and check that the window manager supports it as advertised by
+/// _NET_SUPPORTED e.g. to report unsupported states before sending requests that would be ignored
+/// * Returns an `Unsupported` error naming the state's atom if it isn't supported
+///
+/// ### Arguments
+/// * `name` - EWMH state name with or without the _NET_WM_STATE_ prefix
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let state = libwmctl::check_state_supported("shaded").unwrap();
+/// ```
+pub fn check_state_supported(name: &str) -> WmCtlResult<State> {
+    let state = State::try_from(name)?;
+    WM().read().unwrap().check_state_supported(&state)?;
+    Ok(state)
+}
+
+/// Get the managed window at the given point in screen coordinates e.g. for scripts acting on
+/// whatever is at a spot on the screen without moving the pointer there
+/// * Returns None if only the root or desktop window is at the point
+/// * Returns an error if the point is outside the 16 bit X11 coordinate range
+///
+/// ### Arguments
+/// * `x` - x coordinate of the point in screen coordinates
+/// * `y` - y coordinate of the point in screen coordinates
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let win = libwmctl::window_at(100, 100).unwrap();
+/// ```
+pub fn window_at(x: i32, y: i32) -> WmCtlResult<Option<Window>> {
+    Ok(WM().read().unwrap().window_at(x, y)?.map(Window::new))
+}
+
+/// Get window manager informational properties using the given connection e.g. to a second display
+///
+/// ### Arguments
+/// * `wm` - window manager connection to operate against
+///
+/// ### Examples
+/// ```ignore
+/// use std::sync::{Arc, RwLock};
+/// use libwmctl::prelude::*;
+/// let wm = Arc::new(RwLock::new(WinMgr::new_connection(Some(":1")).unwrap()));
+/// let info = libwmctl::info_on(&wm).unwrap();
+/// ```
+pub fn info_on(wm: &Arc<RwLock<WinMgr>>) -> WmCtlResult<Info> {
+    wm.read().unwrap().info()
+}
+
+/// Get the active window using the given connection e.g. to a second display
+///
+/// ### Arguments
+/// * `wm` - window manager connection to operate against
+///
+/// ### Examples
+/// ```ignore
+/// use std::sync::{Arc, RwLock};
+/// use libwmctl::prelude::*;
+/// let wm = Arc::new(RwLock::new(WinMgr::new_connection(Some(":1")).unwrap()));
+/// let win = libwmctl::active_on(&wm).unwrap();
+/// ```
+pub fn active_on(wm: &Arc<RwLock<WinMgr>>) -> WmCtlResult<Window> {
+    let id = wm.read().unwrap().active_window()?;
+    Ok(Window::with_winmgr(wm, id))
+}
+
+/// Get the active desktop using the given connection e.g. to a second display
+/// * Returns non zero based desktop number to align with `Window::desktop`
+///
+/// ### Arguments
+/// * `wm` - window manager connection to operate against
+///
+/// ### Examples
+/// ```ignore
+/// use std::sync::{Arc, RwLock};
+/// use libwmctl::prelude::*;
+/// let wm = Arc::new(RwLock::new(WinMgr::new_connection(Some(":1")).unwrap()));
+/// let desktop = libwmctl::active_desktop_on(&wm).unwrap();
+/// ```
+pub fn active_desktop_on(wm: &Arc<RwLock<WinMgr>>) -> WmCtlResult<u32> {
+    wm.read().unwrap().current_desktop()
+}
+
+/// Get the number of desktops using the given connection e.g. to a second display
+///
+/// ### Arguments
+/// * `wm` - window manager connection to operate against
+///
+/// ### Examples
+/// ```ignore
+/// use std::sync::{Arc, RwLock};
+/// use libwmctl::prelude::*;
+/// let wm = Arc::new(RwLock::new(WinMgr::new_connection(Some(":1")).unwrap()));
+/// let count = libwmctl::desktops_on(&wm).unwrap();
+/// ```
+pub fn desktops_on(wm: &Arc<RwLock<WinMgr>>) -> WmCtlResult<u32> {
+    wm.read().unwrap().desktops()
+}
+
+/// Get all the windows the window manager is managing using the given connection e.g. to a second
+/// display. The windows operate against the same connection.
+///
+/// ### Arguments
+/// * `wm` - window manager connection to operate against
+/// * `hidden` - when set to true will list all x11 windows not just those the window manager lists
+///
+/// ### Examples
+/// ```ignore
+/// use std::sync::{Arc, RwLock};
+/// use libwmctl::prelude::*;
+/// let wm = Arc::new(RwLock::new(WinMgr::new_connection(Some(":1")).unwrap()));
+/// let windows = libwmctl::windows_on(&wm, false).unwrap();
+/// ```
+pub fn windows_on(wm: &Arc<RwLock<WinMgr>>, hidden: bool) -> WmCtlResult<Vec<Window>> {
+    let ids = wm.read().unwrap().windows(hidden)?;
+    Ok(ids.into_iter().map(|id| Window::with_winmgr(wm, id)).collect())
+}
+
+/// Get the monitors as reported by the RandR extension using the given connection e.g. to a second
+/// display
+/// * Returns an empty list if the RandR extension isn't available
+///
+/// ### Arguments
+/// * `wm` - window manager connection to operate against
+///
+/// ### Examples
+/// ```ignore
+/// use std::sync::{Arc, RwLock};
+/// use libwmctl::prelude::*;
+/// let wm = Arc::new(RwLock::new(WinMgr::new_connection(Some(":1")).unwrap()));
+/// let monitors = libwmctl::monitors_on(&wm).unwrap();
+/// ```
+pub fn monitors_on(wm: &Arc<RwLock<WinMgr>>) -> WmCtlResult<Vec<Monitor>> {
+    wm.read().unwrap().monitors()
+}
+
 /// Get the first window that matches the given class
 ///
 /// ### Arguments
@@ -122,6 +734,43 @@ pub fn first_by_class(class: &str) -> Option<Window> {
         .map_or(None, |x| Some(x.clone()))
 }
 
+/// Get all windows that match the given class
+///
+/// ### Arguments
+/// * `class` - the class to match against
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let windows = libwmctl::all_by_class("firefox");
+/// ```
+pub fn all_by_class(class: &str) -> Vec<Window> {
+    windows(false)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|x| x.class().unwrap_or_default().to_lowercase() == class.to_lowercase())
+        .collect()
+}
+
+/// Get all windows whose name contains the given name ignoring case
+///
+/// ### Arguments
+/// * `name` - the name or part of the name to match against
+///
+/// ### Examples
+/// ```ignore
+/// use libwmctl::prelude::*;
+/// let windows = libwmctl::all_by_name("Mozilla Firefox");
+/// ```
+pub fn all_by_name(name: &str) -> Vec<Window> {
+    let name = name.to_lowercase();
+    windows(false)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|x| x.name().unwrap_or_default().to_lowercase().contains(&name))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
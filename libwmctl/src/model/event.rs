@@ -0,0 +1,30 @@
+use std::fmt;
+
+/// WmEvent
+/// Change notifications raised by the window manager via property changes on the root window e.g.
+/// when the active window or current desktop changes.
+///
+/// <https://specifications.freedesktop.org/wm-spec/latest/ar01s03.html>
+#[derive(Debug, Clone, PartialEq)]
+pub enum WmEvent {
+    ActiveWindow(u32),    // id of the newly active window or 0 if none
+    Desktop(u32),         // non zero based number of the newly current desktop
+    DesktopCount(u32),    // new number of desktops
+    ShowingDesktop(bool), // showing the desktop mode was entered or left
+    ClientList,           // windows were mapped or unmapped
+    WorkArea,             // the work area changed e.g. panel was added or removed
+}
+
+// Implement format! support
+impl fmt::Display for WmEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WmEvent::ActiveWindow(id) => write!(f, "active_window: {}", id),
+            WmEvent::Desktop(desktop) => write!(f, "desktop: {}", desktop),
+            WmEvent::DesktopCount(count) => write!(f, "desktop_count: {}", count),
+            WmEvent::ShowingDesktop(showing) => write!(f, "showing_desktop: {}", showing),
+            WmEvent::ClientList => write!(f, "client_list"),
+            WmEvent::WorkArea => write!(f, "work_area"),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+/// Image provides the pixels of a captured window
+/// * Pixels are stored as 8 bit RGBA values row by row starting at the top left
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: u32,    // width of the image in pixels
+    pub height: u32,   // height of the image in pixels
+    pub depth: u8,     // depth of the captured drawable e.g. 24 or 32
+    pub data: Vec<u8>, // RGBA pixel values
+}
+
+impl Image {
+    /// Save the image to the given path in PNG format
+    ///
+    /// ### Arguments
+    /// * `path` - path to save the image to
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let image = window(12345).capture().unwrap();
+    /// image.save_png("window.png").unwrap();
+    /// ```
+    #[cfg(feature = "png")]
+    pub fn save_png<T: AsRef<std::path::Path>>(&self, path: T) -> Result<(), png::EncodingError> {
+        let file = std::fs::File::create(path)?;
+        let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.data)
+    }
+}
@@ -1,13 +1,47 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Info provides information about the window manager and its environment.
 pub struct Info {
     pub id: u32,
     pub name: String,
     pub compositing: bool,
+    pub compositor: Option<String>,
     pub root_win_id: u32,
     pub work_area: (u32, u32),
     pub screen_size: (u32, u32),
     pub desktops: u32,
-    pub supported: HashMap<u32, String>,
+    pub desktop_geometry: (u32, u32),     // size of the large virtual desktop
+    pub viewport: (i32, i32),             // top left of the current desktop's viewport
+    pub supported: HashMap<u32, String>,  // id => name of the supported atoms
+    pub supported_names: HashSet<String>, // names of the supported atoms for fast lookups
+}
+
+impl Info {
+    /// Get the names of the given atoms that the window manager doesn't advertise as supported
+    ///
+    /// ### Arguments
+    /// * `known` - names of the atoms to check e.g. `ATOM_NAMES`
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let missing = info().unwrap().unsupported(ATOM_NAMES);
+    /// ```
+    pub fn unsupported(&self, known: &[&str]) -> Vec<String> {
+        known.iter().filter(|x| !self.supports(x)).map(|x| x.to_string()).collect()
+    }
+
+    /// Check if the window manager advertises the given atom as supported
+    ///
+    /// ### Arguments
+    /// * `name` - name of the atom to check e.g. _NET_WM_STATE_FULLSCREEN
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let fullscreen = info().unwrap().supports("_NET_WM_STATE_FULLSCREEN");
+    /// ```
+    pub fn supports(&self, name: &str) -> bool {
+        self.supported_names.contains(name)
+    }
 }
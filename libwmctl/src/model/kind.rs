@@ -57,6 +57,17 @@ impl Kind {
             Err(WmCtlError::InvalidWinType(val).into())
         }
     }
+
+    /// Convert all the given _NET_WM_WINDOW_TYPE atoms into kinds in the given order skipping any
+    /// that aren't known e.g. _KDE_NET_WM_WINDOW_TYPE_OVERRIDE
+    /// * Defaults to Normal if none are known as per the EWMH spec for managed windows
+    pub(crate) fn from_all(atoms: &AtomCollection, vals: &[u32]) -> Vec<Kind> {
+        let kinds = vals.iter().filter_map(|x| Kind::from(atoms, *x).ok()).collect::<Vec<_>>();
+        match kinds.is_empty() {
+            true => vec![Kind::Normal],
+            false => kinds,
+        }
+    }
 }
 
 // Implement format! support
@@ -68,3 +79,20 @@ impl fmt::Display for Kind {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_from_all() {
+        let atoms = AtomCollection::test();
+        let kde = 10_000;
+        let vals = [kde, atoms._NET_WM_WINDOW_TYPE_DIALOG, atoms._NET_WM_WINDOW_TYPE_NORMAL];
+        assert_eq!(Kind::from_all(&atoms, &vals), vec![Kind::Dialog, Kind::Normal]);
+
+        // Missing or only unknown types default to normal
+        assert_eq!(Kind::from_all(&atoms, &[]), vec![Kind::Normal]);
+        assert_eq!(Kind::from_all(&atoms, &[kde]), vec![Kind::Normal]);
+    }
+}
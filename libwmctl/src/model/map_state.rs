@@ -35,3 +35,17 @@ impl fmt::Display for MapState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_state_from() {
+        // A freshly created window reports unmapped until it is mapped
+        assert_eq!(MapState::from(xproto::MapState::UNMAPPED.into()).unwrap(), MapState::Unmapped);
+        assert_eq!(MapState::from(xproto::MapState::UNVIEWABLE.into()).unwrap(), MapState::Unviewable);
+        assert_eq!(MapState::from(xproto::MapState::VIEWABLE.into()).unwrap(), MapState::Viewable);
+        assert!(MapState::from(3).is_err());
+    }
+}
@@ -4,24 +4,38 @@
 //! ```
 //! use libwmctl::prelude::*;
 //! ```
+mod event;
 mod gravity;
+mod image;
 mod info;
 mod kind;
 mod map_state;
+mod monitor;
+mod motif;
 mod position;
 mod property;
+mod resize_edge;
 mod shape;
 mod state;
+mod win_class;
 
 // Export contents of modules
+pub use event::*;
 pub use gravity::*;
+pub use image::*;
 pub use info::*;
 pub use kind::*;
 pub use map_state::*;
+pub use monitor::*;
+pub use motif::*;
 pub use position::*;
 pub use property::*;
+pub use resize_edge::*;
 pub use shape::*;
 pub use state::*;
+pub use win_class::*;
+
+use std::fmt;
 
 // Define the second byte of the move resize flags 32bit value
 // Used to indicate that the associated value has been changed and needs to be acted upon
@@ -34,6 +48,17 @@ pub const MOVE_RESIZE_WINDOW_HEIGHT: MoveResizeWindowFlags = 1 << 11;
 pub type WindowStateAction = u32;
 pub const WINDOW_STATE_ACTION_REMOVE: WindowStateAction = 0;
 pub const WINDOW_STATE_ACTION_ADD: WindowStateAction = 1;
+pub const WINDOW_STATE_ACTION_TOGGLE: WindowStateAction = 2;
+
+// Special _NET_WM_DESKTOP value indicating the window should be shown on all desktops
+pub const ALL_DESKTOPS: u32 = 0xFFFFFFFF;
+
+// Define the source indication sent with client messages to the window manager. Some window
+// managers ignore messages from sources they don't recognize e.g. the legacy value of 0.
+pub type SourceIndication = u32;
+pub const SOURCE_INDICATION_LEGACY: SourceIndication = 0;
+pub const SOURCE_INDICATION_APPLICATION: SourceIndication = 1;
+pub const SOURCE_INDICATION_PAGER: SourceIndication = 2;
 
 /// Border provides a simple way to store border values
 #[derive(Default)]
@@ -77,3 +102,182 @@ impl Rect {
         Self { w, h }
     }
 }
+
+/// Geometry provides a simple way to store the position and size of an area
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Geometry {
+    pub x: i32,
+    pub y: i32,
+    pub w: u32,
+    pub h: u32,
+}
+
+impl Geometry {
+    pub fn new(x: i32, y: i32, w: u32, h: u32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    // Center point of the area
+    pub fn center(&self) -> (i32, i32) {
+        (self.x + (self.w / 2) as i32, self.y + (self.h / 2) as i32)
+    }
+
+    // Check if the given point lies within the area
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.x + self.w as i32 && y >= self.y && y < self.y + self.h as i32
+    }
+
+    // Overlapping area of the two geometries if any
+    pub fn intersect(&self, other: &Geometry) -> Option<Geometry> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let r = (self.x + self.w as i32).min(other.x + other.w as i32);
+        let b = (self.y + self.h as i32).min(other.y + other.h as i32);
+        if r <= x || b <= y {
+            return None;
+        }
+        Some(Geometry::new(x, y, (r - x) as u32, (b - y) as u32))
+    }
+
+    // Check if the geometries match allowing each value to differ by up to the given tolerance
+    // e.g. for the few pixels of drift some window managers introduce when placing windows
+    pub fn approx_eq(&self, other: &Geometry, tol: u32) -> bool {
+        self.x.abs_diff(other.x) <= tol
+            && self.y.abs_diff(other.y) <= tol
+            && self.w.abs_diff(other.w) <= tol
+            && self.h.abs_diff(other.h) <= tol
+    }
+
+    // Difference from this geometry to the other e.g. to report how a window moved when placed
+    // * Computed as i64 so that no difference between i32 positions or u32 sizes can overflow
+    pub fn delta(&self, other: &Geometry) -> GeometryDelta {
+        GeometryDelta {
+            dx: other.x as i64 - self.x as i64,
+            dy: other.y as i64 - self.y as i64,
+            dw: other.w as i64 - self.w as i64,
+            dh: other.h as i64 - self.h as i64,
+        }
+    }
+
+    // Shrink and shift the geometry as needed to fit within the given area
+    pub fn clamp(&self, area: &Geometry) -> Geometry {
+        let w = self.w.min(area.w);
+        let h = self.h.min(area.h);
+        let x = self.x.min(area.x + (area.w - w) as i32).max(area.x);
+        let y = self.y.min(area.y + (area.h - h) as i32).max(area.y);
+        Geometry::new(x, y, w, h)
+    }
+}
+
+impl From<(i32, i32, u32, u32)> for Geometry {
+    fn from(val: (i32, i32, u32, u32)) -> Self {
+        Geometry::new(val.0, val.1, val.2, val.3)
+    }
+}
+
+impl From<Geometry> for (i32, i32, u32, u32) {
+    fn from(val: Geometry) -> Self {
+        (val.x, val.y, val.w, val.h)
+    }
+}
+
+/// GeometryDelta provides the difference between two geometries
+/// * Positive values mean moved right or down and grew wider or taller
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct GeometryDelta {
+    pub dx: i64,
+    pub dy: i64,
+    pub dw: i64,
+    pub dh: i64,
+}
+
+impl GeometryDelta {
+    // Check if the geometries were the same
+    pub fn is_zero(&self) -> bool {
+        self.dx == 0 && self.dy == 0 && self.dw == 0 && self.dh == 0
+    }
+}
+
+// Implement format! support e.g. moved 100px right, grew 50px wide
+impl fmt::Display for GeometryDelta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "unchanged");
+        }
+        let mut parts = vec![];
+        match self.dx {
+            x if x > 0 => parts.push(format!("moved {}px right", x)),
+            x if x < 0 => parts.push(format!("moved {}px left", x.unsigned_abs())),
+            _ => (),
+        }
+        match self.dy {
+            y if y > 0 => parts.push(format!("moved {}px down", y)),
+            y if y < 0 => parts.push(format!("moved {}px up", y.unsigned_abs())),
+            _ => (),
+        }
+        match self.dw {
+            w if w > 0 => parts.push(format!("grew {}px wide", w)),
+            w if w < 0 => parts.push(format!("shrank {}px wide", w.unsigned_abs())),
+            _ => (),
+        }
+        match self.dh {
+            h if h > 0 => parts.push(format!("grew {}px tall", h)),
+            h if h < 0 => parts.push(format!("shrank {}px tall", h.unsigned_abs())),
+            _ => (),
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geometry_approx_eq() {
+        let g = Geometry::new(100, 200, 800, 600);
+        assert!(g.approx_eq(&g, 0));
+
+        // Drift right at the tolerance on each value
+        assert!(g.approx_eq(&Geometry::new(104, 200, 800, 600), 4));
+        assert!(g.approx_eq(&Geometry::new(100, 196, 800, 600), 4));
+        assert!(g.approx_eq(&Geometry::new(100, 200, 804, 596), 4));
+
+        // Drift just past the tolerance on any value
+        assert!(!g.approx_eq(&Geometry::new(105, 200, 800, 600), 4));
+        assert!(!g.approx_eq(&Geometry::new(100, 200, 800, 595), 4));
+
+        // Negative positions e.g. monitors left of the primary
+        assert!(Geometry::new(-2, -1, 10, 10).approx_eq(&Geometry::new(1, 1, 10, 10), 3));
+        assert!(!Geometry::new(-2, 0, 10, 10).approx_eq(&Geometry::new(2, 0, 10, 10), 3));
+    }
+
+    #[test]
+    fn test_geometry_delta() {
+        let g = Geometry::new(100, 200, 800, 600);
+        assert!(g.delta(&g).is_zero());
+        assert_eq!(g.delta(&g).to_string(), "unchanged");
+
+        let delta = g.delta(&Geometry::new(200, 150, 850, 600));
+        assert_eq!(
+            delta,
+            GeometryDelta {
+                dx: 100,
+                dy: -50,
+                dw: 50,
+                dh: 0
+            }
+        );
+        assert_eq!(delta.to_string(), "moved 100px right, moved 50px up, grew 50px wide");
+        assert_eq!(
+            Geometry::new(0, 0, 800, 600).delta(&Geometry::new(0, 0, 800, 500)).to_string(),
+            "shrank 100px tall"
+        );
+
+        // Extremes don't overflow
+        let delta =
+            Geometry::new(i32::MIN, i32::MAX, 0, u32::MAX).delta(&Geometry::new(i32::MAX, i32::MIN, u32::MAX, 0));
+        assert_eq!((delta.dx, delta.dy), (u32::MAX as i64, -(u32::MAX as i64)));
+        assert_eq!((delta.dw, delta.dh), (u32::MAX as i64, -(u32::MAX as i64)));
+    }
+}
@@ -0,0 +1,10 @@
+use super::Geometry;
+
+/// Monitor provides the details of a physical display as reported by the RandR extension
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    pub name: String,        // output name e.g. HDMI-1
+    pub primary: bool,       // primary monitor
+    pub geometry: Geometry,  // full area of the monitor in screen coordinates
+    pub work_area: Geometry, // area of the monitor not covered by panels
+}
@@ -0,0 +1,77 @@
+// Define the flags indicating which of the Motif hints fields are valid
+pub const MOTIF_HINTS_FUNCTIONS: u32 = 1 << 0;
+pub const MOTIF_HINTS_DECORATIONS: u32 = 1 << 1;
+
+// Define the decoration flags of the Motif hints decorations field
+pub const MOTIF_DECOR_ALL: u32 = 1 << 0;
+
+/// MotifHints provides the layout of the _MOTIF_WM_HINTS property which many window managers honor
+/// for adding or removing the window's titlebar and border decorations.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MotifHints {
+    pub flags: u32,       // which of the following fields are valid
+    pub functions: u32,   // window manager functions to allow e.g. move, resize
+    pub decorations: u32, // window manager decorations to show e.g. border, title
+    pub input_mode: i32,  // modal input mode
+    pub status: u32,      // tear off menu status
+}
+
+impl MotifHints {
+    /// Create the hints from the raw property values padding any missing values with zero
+    pub fn from(vals: &[u32]) -> Self {
+        let val = |i: usize| vals.get(i).copied().unwrap_or(0);
+        Self {
+            flags: val(0),
+            functions: val(1),
+            decorations: val(2),
+            input_mode: val(3) as i32,
+            status: val(4),
+        }
+    }
+
+    /// Convert the hints into raw property values
+    pub fn to_vec(&self) -> Vec<u32> {
+        vec![
+            self.flags,
+            self.functions,
+            self.decorations,
+            self.input_mode as u32,
+            self.status,
+        ]
+    }
+
+    /// Check if the hints allow decorations. Decorations are on unless explicitly turned off.
+    pub fn decorated(&self) -> bool {
+        self.flags & MOTIF_HINTS_DECORATIONS == 0 || self.decorations != 0
+    }
+
+    /// Set the decorations to either all or none
+    pub fn set_decorated(&mut self, decorated: bool) {
+        self.flags |= MOTIF_HINTS_DECORATIONS;
+        self.decorations = if decorated { MOTIF_DECOR_ALL } else { 0 };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_motif_hints_decorated() {
+        // Missing hints default to decorated
+        let mut hints = MotifHints::from(&[]);
+        assert!(hints.decorated());
+
+        // Functions are preserved when changing decorations
+        hints = MotifHints::from(&[MOTIF_HINTS_FUNCTIONS, 4, 0, 0, 0]);
+        assert!(hints.decorated());
+        hints.set_decorated(false);
+        assert!(!hints.decorated());
+        assert_eq!(hints.to_vec(), vec![MOTIF_HINTS_FUNCTIONS | MOTIF_HINTS_DECORATIONS, 4, 0, 0, 0]);
+        hints.set_decorated(true);
+        assert_eq!(
+            hints.to_vec(),
+            vec![MOTIF_HINTS_FUNCTIONS | MOTIF_HINTS_DECORATIONS, 4, MOTIF_DECOR_ALL, 0, 0]
+        );
+    }
+}
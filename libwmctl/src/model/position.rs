@@ -21,6 +21,36 @@ pub enum Position {
     Static(i32, i32),
 }
 
+// Names accepted when parsing from a string in the order they are listed to the user
+const NAMES: &[(&str, Position)] = &[
+    ("center", Position::Center),
+    ("left", Position::Left),
+    ("right", Position::Right),
+    ("top", Position::Top),
+    ("bottom", Position::Bottom),
+    ("top-left", Position::TopLeft),
+    ("top-right", Position::TopRight),
+    ("bottom-left", Position::BottomLeft),
+    ("bottom-right", Position::BottomRight),
+    ("left-center", Position::LeftCenter),
+    ("right-center", Position::RightCenter),
+    ("top-center", Position::TopCenter),
+    ("bottom-center", Position::BottomCenter),
+];
+
+impl Position {
+    /// Get the names accepted when parsing from a string
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// assert!(Position::names().contains(&"center"));
+    /// ```
+    pub fn names() -> Vec<&'static str> {
+        NAMES.iter().map(|(name, _)| *name).collect()
+    }
+}
+
 // Implement format! support
 impl fmt::Display for Position {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -35,21 +65,15 @@ impl convert::TryFrom<&str> for Position {
     type Error = WmCtlError;
 
     fn try_from(val: &str) -> Result<Self, Self::Error> {
-        match val.to_lowercase().as_ref() {
-            "center" => Ok(Position::Center),
-            "left" => Ok(Position::Left),
-            "right" => Ok(Position::Right),
-            "top" => Ok(Position::Top),
-            "bottom" => Ok(Position::Bottom),
-            "top-left" => Ok(Position::TopLeft),
-            "top-right" => Ok(Position::TopRight),
-            "bottom-left" => Ok(Position::BottomLeft),
-            "bottom-right" => Ok(Position::BottomRight),
-            "left-center" => Ok(Position::LeftCenter),
-            "right-center" => Ok(Position::RightCenter),
-            "top-center" => Ok(Position::TopCenter),
-            "bottom-center" => Ok(Position::BottomCenter),
-            _ => Err(WmCtlError::InvalidWinPosition(val.to_string()).into()),
+        let val_lower = val.to_lowercase();
+        match NAMES.iter().find(|(name, _)| *name == val_lower) {
+            Some((_, position)) => Ok(position.clone()),
+            None => Err(WmCtlError::InvalidWinPosition(format!(
+                "{}, expected one of: {}",
+                val,
+                Position::names().join(", ")
+            ))
+            .into()),
         }
     }
 }
@@ -62,3 +86,15 @@ impl convert::TryFrom<String> for Position {
         Position::try_from(val.as_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_parse_error_lists_names() {
+        assert_eq!(Position::try_from("top-left").unwrap(), Position::TopLeft);
+        let err = Position::try_from("middle").unwrap_err().to_string();
+        assert!(err.contains("middle") && Position::names().iter().all(|x| err.contains(x)));
+    }
+}
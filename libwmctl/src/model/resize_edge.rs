@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// ResizeEdge
+/// Direction of an interactive move or resize handed off to the window manager via the
+/// _NET_WM_MOVERESIZE client message e.g. when dragging a client side decorated titlebar or edge.
+///
+/// <https://specifications.freedesktop.org/wm-spec/latest/ar01s04.html>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResizeEdge {
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+    Move,         // move the window with the pointer
+    SizeKeyboard, // resize the window with the keyboard
+    MoveKeyboard, // move the window with the keyboard
+    Cancel,       // cancel the move or resize in progress
+}
+
+// Implement format! support
+impl fmt::Display for ResizeEdge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_lowercase())
+    }
+}
+
+impl From<ResizeEdge> for u32 {
+    fn from(val: ResizeEdge) -> Self {
+        match val {
+            ResizeEdge::TopLeft => 0,
+            ResizeEdge::Top => 1,
+            ResizeEdge::TopRight => 2,
+            ResizeEdge::Right => 3,
+            ResizeEdge::BottomRight => 4,
+            ResizeEdge::Bottom => 5,
+            ResizeEdge::BottomLeft => 6,
+            ResizeEdge::Left => 7,
+            ResizeEdge::Move => 8,
+            ResizeEdge::SizeKeyboard => 9,
+            ResizeEdge::MoveKeyboard => 10,
+            ResizeEdge::Cancel => 11,
+        }
+    }
+}
@@ -16,6 +16,33 @@ pub enum Shape {
     Square,
     UnMax,
     Static(u32, u32),
+    Aspect(u32, u32, f32), // width ratio, height ratio, fraction of the short side to fill
+}
+
+// Names accepted when parsing from a string in the order they are listed to the user
+const NAMES: &[(&str, Shape)] = &[
+    ("grow", Shape::Grow),
+    ("max", Shape::Max),
+    ("halfw", Shape::Halfw),
+    ("halfh", Shape::Halfh),
+    ("small", Shape::Small),
+    ("medium", Shape::Medium),
+    ("large", Shape::Large),
+    ("shrink", Shape::Shrink),
+    ("unmax", Shape::UnMax),
+];
+
+impl Shape {
+    /// Get the names accepted when parsing from a string
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// assert!(Shape::names().contains(&"grow"));
+    /// ```
+    pub fn names() -> Vec<&'static str> {
+        NAMES.iter().map(|(name, _)| *name).collect()
+    }
 }
 
 // Implement format! support
@@ -32,17 +59,15 @@ impl convert::TryFrom<&str> for Shape {
     type Error = WmCtlError;
 
     fn try_from(val: &str) -> Result<Self, Self::Error> {
-        match val.to_lowercase().as_ref() {
-            "grow" => Ok(Shape::Grow),
-            "max" => Ok(Shape::Max),
-            "halfw" => Ok(Shape::Halfw),
-            "halfh" => Ok(Shape::Halfh),
-            "small" => Ok(Shape::Small),
-            "medium" => Ok(Shape::Medium),
-            "large" => Ok(Shape::Large),
-            "shrink" => Ok(Shape::Shrink),
-            "unmax" => Ok(Shape::UnMax),
-            _ => Err(WmCtlError::InvalidWinShape(val.to_string()).into()),
+        let val_lower = val.to_lowercase();
+        match NAMES.iter().find(|(name, _)| *name == val_lower) {
+            Some((_, shape)) => Ok(shape.clone()),
+            None => Err(WmCtlError::InvalidWinShape(format!(
+                "{}, expected one of: {}",
+                val,
+                Shape::names().join(", ")
+            ))
+            .into()),
         }
     }
 }
@@ -55,3 +80,15 @@ impl convert::TryFrom<String> for Shape {
         Shape::try_from(val.as_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape_parse_error_lists_names() {
+        assert_eq!(Shape::try_from("MAX").unwrap(), Shape::Max);
+        let err = Shape::try_from("huge").unwrap_err().to_string();
+        assert!(err.contains("huge") && Shape::names().iter().all(|x| err.contains(x)));
+    }
+}
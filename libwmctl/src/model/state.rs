@@ -1,5 +1,5 @@
 use crate::{atoms::AtomCollection, WmCtlError, WmCtlResult};
-use std::fmt;
+use std::{collections::HashMap, convert, fmt};
 
 /// State provides an easy way to identify the different window states
 #[allow(dead_code)]
@@ -54,6 +54,74 @@ impl State {
             Err(WmCtlError::InvalidWinState(val).into())
         }
     }
+
+    /// Convert the state into its _NET_WM_STATE atom
+    pub(crate) fn atom(&self, atoms: &AtomCollection) -> WmCtlResult<u32> {
+        Ok(match self {
+            State::Above => atoms._NET_WM_STATE_ABOVE,
+            State::Below => atoms._NET_WM_STATE_BELOW,
+            State::DemandsAttention => atoms._NET_WM_STATE_DEMANDS_ATTENTION,
+            State::Focused => atoms._NET_WM_STATE_FOCUSED,
+            State::Fullscreen => atoms._NET_WM_STATE_FULLSCREEN,
+            State::Hidden => atoms._NET_WM_STATE_HIDDEN,
+            State::MaxHorz => atoms._NET_WM_STATE_MAXIMIZED_HORZ,
+            State::MaxVert => atoms._NET_WM_STATE_MAXIMIZED_VERT,
+            State::Modal => atoms._NET_WM_STATE_MODAL,
+            State::Shaded => atoms._NET_WM_STATE_SHADED,
+            State::SkipPager => atoms._NET_WM_STATE_SKIP_PAGER,
+            State::SkipTaskbar => atoms._NET_WM_STATE_SKIP_TASKBAR,
+            State::Sticky => atoms._NET_WM_STATE_STICKY,
+            State::Invalid => return Err(WmCtlError::InvalidWinStateName(self.to_string()).into()),
+        })
+    }
+
+    /// Get the name of the state's _NET_WM_STATE atom e.g. _NET_WM_STATE_FULLSCREEN
+    pub fn atom_name(&self) -> &'static str {
+        match self {
+            State::Above => "_NET_WM_STATE_ABOVE",
+            State::Below => "_NET_WM_STATE_BELOW",
+            State::DemandsAttention => "_NET_WM_STATE_DEMANDS_ATTENTION",
+            State::Focused => "_NET_WM_STATE_FOCUSED",
+            State::Fullscreen => "_NET_WM_STATE_FULLSCREEN",
+            State::Hidden => "_NET_WM_STATE_HIDDEN",
+            State::MaxHorz => "_NET_WM_STATE_MAXIMIZED_HORZ",
+            State::MaxVert => "_NET_WM_STATE_MAXIMIZED_VERT",
+            State::Modal => "_NET_WM_STATE_MODAL",
+            State::Shaded => "_NET_WM_STATE_SHADED",
+            State::SkipPager => "_NET_WM_STATE_SKIP_PAGER",
+            State::SkipTaskbar => "_NET_WM_STATE_SKIP_TASKBAR",
+            State::Sticky => "_NET_WM_STATE_STICKY",
+            State::Invalid => "",
+        }
+    }
+
+    /// Check that the state is in the given set of supported atoms e.g. as advertised by the window
+    /// manager's _NET_SUPPORTED
+    /// * Returns an `Unsupported` error naming the state's atom if it isn't supported
+    pub(crate) fn check_supported(
+        &self, atoms: &AtomCollection, supported: &HashMap<u32, String>,
+    ) -> WmCtlResult<()> {
+        match supported.contains_key(&self.atom(atoms)?) {
+            true => Ok(()),
+            false => Err(WmCtlError::Unsupported(self.atom_name().to_owned()).into()),
+        }
+    }
+
+    /// Convert all the given atoms into states skipping any that aren't known
+    ///
+    /// ### Returns
+    /// * `(states, unknown)` known states and the atoms that didn't map to a state
+    pub(crate) fn from_all(atoms: &AtomCollection, vals: &[u32]) -> (Vec<State>, Vec<u32>) {
+        let mut states = vec![];
+        let mut unknown = vec![];
+        for val in vals.iter() {
+            match State::from(atoms, *val) {
+                Ok(state) => states.push(state),
+                Err(_) => unknown.push(*val),
+            }
+        }
+        (states, unknown)
+    }
 }
 
 // Implement format! support
@@ -65,3 +133,76 @@ impl fmt::Display for State {
         }
     }
 }
+
+// Convert from &str to State accepting both the EWMH names e.g. maximized_vert and the display
+// names e.g. maxvert with an optional _NET_WM_STATE_ prefix
+impl convert::TryFrom<&str> for State {
+    type Error = WmCtlError;
+
+    fn try_from(val: &str) -> Result<Self, Self::Error> {
+        let name = val.to_lowercase().replace('-', "_");
+        match name.strip_prefix("_net_wm_state_").unwrap_or(&name) {
+            "above" => Ok(State::Above),
+            "below" => Ok(State::Below),
+            "demands_attention" | "demandsattention" => Ok(State::DemandsAttention),
+            "focused" => Ok(State::Focused),
+            "fullscreen" => Ok(State::Fullscreen),
+            "hidden" => Ok(State::Hidden),
+            "maximized_horz" | "maxhorz" => Ok(State::MaxHorz),
+            "maximized_vert" | "maxvert" => Ok(State::MaxVert),
+            "modal" => Ok(State::Modal),
+            "shaded" => Ok(State::Shaded),
+            "skip_pager" | "skippager" => Ok(State::SkipPager),
+            "skip_taskbar" | "skiptaskbar" => Ok(State::SkipTaskbar),
+            "sticky" => Ok(State::Sticky),
+            _ => Err(WmCtlError::InvalidWinStateName(val.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_from_all_skips_unknown() {
+        let atoms = AtomCollection::test();
+        let kde = 10_000;
+        let vals = [atoms._NET_WM_STATE_ABOVE, kde, atoms._NET_WM_STATE_STICKY, kde + 1];
+        let (states, unknown) = State::from_all(&atoms, &vals);
+        assert_eq!(states, vec![State::Above, State::Sticky]);
+        assert_eq!(unknown, vec![kde, kde + 1]);
+
+        // Only unknown states
+        let (states, unknown) = State::from_all(&atoms, &[kde]);
+        assert!(states.is_empty());
+        assert_eq!(unknown, vec![kde]);
+    }
+
+    #[test]
+    fn test_state_try_from_name() {
+        let atoms = AtomCollection::test();
+        assert_eq!(State::try_from("fullscreen").unwrap(), State::Fullscreen);
+        assert_eq!(State::try_from("maximized_vert").unwrap(), State::MaxVert);
+        assert_eq!(State::try_from("_NET_WM_STATE_SKIP_TASKBAR").unwrap(), State::SkipTaskbar);
+        assert_eq!(State::try_from("demands-attention").unwrap(), State::DemandsAttention);
+        assert_eq!(State::try_from("bogus").unwrap_err(), WmCtlError::InvalidWinStateName("bogus".to_owned()));
+
+        // Round trip through the atom
+        let state = State::try_from("sticky").unwrap();
+        assert_eq!(State::from(&atoms, state.atom(&atoms).unwrap()).unwrap(), State::Sticky);
+    }
+
+    #[test]
+    fn test_state_check_supported() {
+        let atoms = AtomCollection::test();
+        let supported: HashMap<u32, String> =
+            [(atoms._NET_WM_STATE_FULLSCREEN, "_NET_WM_STATE_FULLSCREEN".to_owned())].into_iter().collect();
+        assert!(State::Fullscreen.check_supported(&atoms, &supported).is_ok());
+
+        // Unsupported states name the state's atom
+        let err = State::Shaded.check_supported(&atoms, &supported).unwrap_err();
+        assert_eq!(err.to_string(), "window manager doesn't support _NET_WM_STATE_SHADED");
+        assert!(State::Invalid.check_supported(&atoms, &supported).is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+use x11rb::protocol::xproto;
+
+use crate::{WmCtlError, WmCtlResult};
+
+/// WinClass provides an easy way to identify the different X window classes. InputOnly windows
+/// are invisible and only used for receiving input so the window manager never manages them.
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum WinClass {
+    CopyFromParent,
+    InputOutput,
+    InputOnly,
+}
+
+// Convert from u32 to class
+impl WinClass {
+    pub fn from(val: u32) -> WmCtlResult<WinClass> {
+        if val == xproto::WindowClass::COPY_FROM_PARENT.into() {
+            Ok(WinClass::CopyFromParent)
+        } else if val == xproto::WindowClass::INPUT_OUTPUT.into() {
+            Ok(WinClass::InputOutput)
+        } else if val == xproto::WindowClass::INPUT_ONLY.into() {
+            Ok(WinClass::InputOnly)
+        } else {
+            Err(WmCtlError::InvalidWinClass(val.to_string()).into())
+        }
+    }
+}
+
+// Implement format! support
+impl fmt::Display for WinClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_lowercase())
+    }
+}
@@ -0,0 +1,142 @@
+//! Watch the window manager for changes
+//!
+//! ### How to use the `watch` module
+//! ```ignore
+//! use libwmctl::prelude::*;
+//! for event in libwmctl::watch().unwrap() {
+//!     println!("{}", event.unwrap());
+//! }
+//! ```
+use crate::{model::*, WinMgr, WmCtlResult};
+
+/// Watcher provides a blocking iterator over window manager events. It uses its own connection to
+/// the X server so that waiting on events doesn't block calls made via the global connection.
+/// * The iterator ends after the first connection error e.g. when the X server goes away
+pub struct Watcher {
+    wm: WinMgr,
+    done: bool,
+}
+
+impl Watcher {
+    /// Create a new watcher with its own connection to the X server
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let watcher = Watcher::new().unwrap();
+    /// ```
+    pub fn new() -> WmCtlResult<Self> {
+        let wm = WinMgr::new_connection(None)?;
+        wm.watch_root()?;
+        Ok(Self { wm, done: false })
+    }
+}
+
+// Block on each call to next until the window manager raises an event
+impl Iterator for Watcher {
+    type Item = WmCtlResult<WmEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let event = self.wm.next_event();
+        self.done = is_lost(&event);
+        Some(event)
+    }
+}
+
+// Check if the result failed due to the connection to the X server being lost which no further
+// events will ever arrive on
+fn is_lost<T>(result: &WmCtlResult<T>) -> bool {
+    result.as_ref().is_err_and(|x| x.is_connection_error())
+}
+
+/// WatchStream provides an async stream of window manager events for integration into async event
+/// loops e.g. tokio. The stream waits on its connection to the X server becoming readable using the
+/// async-io reactor rather than a thread of its own so it works with any executor.
+/// * The stream ends after the first connection error e.g. when the X server goes away
+#[cfg(feature = "async")]
+pub struct WatchStream {
+    io: async_io::Async<EventSource>,
+    done: bool,
+}
+
+// Exposes the connection's file descriptor for readiness polling
+#[cfg(feature = "async")]
+struct EventSource(WinMgr);
+
+#[cfg(feature = "async")]
+impl std::os::fd::AsFd for EventSource {
+    fn as_fd(&self) -> std::os::fd::BorrowedFd<'_> {
+        self.0.conn_fd()
+    }
+}
+
+#[cfg(feature = "async")]
+impl WatchStream {
+    /// Create a new stream of window manager events
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let stream = WatchStream::new().unwrap();
+    /// ```
+    pub fn new() -> WmCtlResult<Self> {
+        let wm = WinMgr::new_connection(None)?;
+        wm.watch_root()?;
+        let io = async_io::Async::new(EventSource(wm)).map_err(x11rb::errors::ConnectionError::IoError)?;
+        Ok(Self { io, done: false })
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures_core::Stream for WatchStream {
+    type Item = WmCtlResult<WmEvent>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        use std::task::Poll;
+
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        loop {
+            // Events may already be queued by the connection so check before waiting on the socket
+            match this.io.get_ref().0.poll_event() {
+                Ok(Some(event)) => return Poll::Ready(Some(Ok(event))),
+                Ok(None) => (),
+                Err(err) => {
+                    this.done = err.is_connection_error();
+                    return Poll::Ready(Some(Err(err)));
+                },
+            }
+            match this.io.poll_readable(cx) {
+                Poll::Ready(Ok(())) => (),
+                Poll::Ready(Err(err)) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(x11rb::errors::ConnectionError::IoError(err).into())));
+                },
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorWrapper, WmCtlError};
+    use x11rb::errors::ConnectionError;
+
+    #[test]
+    fn test_is_lost() {
+        assert!(!is_lost(&Ok(WmEvent::ClientList)));
+        let err: WmCtlResult<()> = Err(WmCtlError::PropertyNotFound("_NET_ACTIVE_WINDOW".to_owned()).into());
+        assert!(!is_lost(&err));
+        let err: WmCtlResult<()> = Err(ErrorWrapper::from(ConnectionError::UnknownError));
+        assert!(is_lost(&err));
+    }
+}
@@ -1,23 +1,79 @@
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use std::time::{Duration, Instant};
 use tracing::debug;
+use x11rb::protocol::xproto::{AtomEnum, StackMode};
 
-use crate::{model::*, WmCtlResult, WM};
+use crate::{model::*, WinMgr, WmCtlError, WmCtlResult, WM};
 
 /// Window provides a higer level interfacefor manipulating windows.
+/// * Reads e.g. geometry() and state() always query the X server so they reflect the window's
+///   current values even immediately after the window was moved. Window managers apply changes
+///   asynchronously though so refresh() can be used to wait for pending requests to be processed.
 #[derive(Clone)]
 pub struct Window {
     pub id: u32,
+    wm: Option<Arc<RwLock<WinMgr>>>, // window manager connection or None for the global connection
 
     // Directives
     shape: Option<Shape>,
     pos: Option<Position>,
+    monitor: Option<String>,
+    clamp: bool,
+    struts: bool,
+
+    // Raw (x, y, w, h) last requested by place() for verification
+    placed: Placed,
+}
+
+// Raw geometry values requested of the window manager with None meaning unchanged
+type RawPlacement = (Option<i32>, Option<i32>, Option<u32>, Option<u32>);
+
+// Last requested placement guarded by a mutex so that Window stays Sync and can be shared across
+// threads. Clones get their own copy of the value as they are placed independently.
+#[derive(Default)]
+struct Placed(Mutex<Option<RawPlacement>>);
+
+impl Placed {
+    fn get(&self) -> Option<RawPlacement> {
+        *self.0.lock().unwrap()
+    }
+
+    fn set(&self, val: Option<RawPlacement>) {
+        *self.0.lock().unwrap() = val;
+    }
 }
 
+impl Clone for Placed {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.get()))
+    }
+}
+
+// Number of pixels the actual geometry may differ from the requested geometry and still be
+// considered a match as window managers may adjust sizes e.g. to respect size increments.
+const PLACEMENT_TOLERANCE: u32 = 16;
+
+// Number of times and interval to check if the window has settled into a requested geometry
+const SETTLE_ATTEMPTS: u32 = 10;
+const SETTLE_INTERVAL: Duration = Duration::from_millis(20);
+
+// How long to wait for the window manager to apply a _NET_WM_STATE change before giving up
+const STATE_TIMEOUT: Duration = Duration::from_secs(1);
+
+// Smallest fraction center_fraction() accepts as anything smaller leaves an unusably small window
+const CENTER_FRACTION_MIN: f32 = 0.1;
+
 impl Window {
     pub(crate) fn new(id: u32) -> Self {
         Self {
             id,
+            wm: None,
             shape: None,
             pos: None,
+            monitor: None,
+            clamp: false,
+            struts: false,
+            placed: Placed::default(),
         }
     }
 
@@ -27,7 +83,36 @@ impl Window {
         Window::new(id)
     }
 
+    /// Create a window that operates against the given window manager connection rather than the
+    /// global connection e.g. to work with multiple X displays.
+    ///
+    /// ### Arguments
+    /// * `wm` - window manager connection to operate against
+    /// * `id` - id of the window
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use std::sync::{Arc, RwLock};
+    /// use libwmctl::prelude::*;
+    /// let wm = Arc::new(RwLock::new(WinMgr::new_connection(Some(":1")).unwrap()));
+    /// let win = Window::with_winmgr(&wm, 12345);
+    /// ```
+    pub fn with_winmgr(wm: &Arc<RwLock<WinMgr>>, id: u32) -> Self {
+        let mut win = Window::new(id);
+        win.wm = Some(wm.clone());
+        win
+    }
+
+    /// Get the window manager connection this window operates against
+    fn wm(&self) -> RwLockReadGuard<'_, WinMgr> {
+        match &self.wm {
+            Some(wm) => wm.read().unwrap(),
+            None => WM().read().unwrap(),
+        }
+    }
+
     /// Get window pid
+    /// * Falls back on the window's frame then the window it is transient for if not set directly
     ///
     /// ### Examples
     /// ```ignore
@@ -36,7 +121,107 @@ impl Window {
     /// let pid = win.pid().unwrap();
     /// ```
     pub fn pid(&self) -> WmCtlResult<i32> {
-        WM().read().unwrap().window_pid(self.id)
+        self.wm().window_pid(self.id)
+    }
+
+    /// Get the name of the executable that owns the window from `/proc/<pid>/comm`
+    /// * Useful for matching apps with unhelpful WM_CLASS values by their binary instead
+    /// * Returns None on non Linux systems or when the window doesn't define a pid
+    /// * The kernel truncates the name to 15 characters
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let name = win.process_name().unwrap();
+    /// ```
+    pub fn process_name(&self) -> WmCtlResult<Option<String>> {
+        if !cfg!(target_os = "linux") {
+            return Ok(None);
+        }
+        let pid = match self.pid() {
+            Ok(pid) => pid,
+            Err(err) if matches!(err.downcast_ref::<WmCtlError>(), Some(WmCtlError::PropertyNotFound(_))) => {
+                return Ok(None)
+            },
+            Err(err) => return Err(err),
+        };
+
+        // The process may have exited since the window was created
+        let name = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok().map(|x| x.trim_end().to_owned());
+        debug!("process_name: id: {}, pid: {}, name: {:?}", self.id, pid, name);
+        Ok(name)
+    }
+
+    /// Get the pid of the application's main process by walking the window's process ancestry while
+    /// the ancestors are the same program e.g. for Chromium and Electron apps whose window pid may
+    /// be a helper process. Useful for reliably closing all windows of a multiprocess app.
+    /// * The walk stops at the first ancestor whose name or executable differs e.g. the panel or
+    ///   desktop session that launched the app and never leaves the window's session
+    /// * Returns None on non Linux systems, when the window doesn't define a pid or the process
+    ///   no longer exists
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let pid = win.root_pid().unwrap();
+    /// ```
+    pub fn root_pid(&self) -> WmCtlResult<Option<i32>> {
+        if !cfg!(target_os = "linux") {
+            return Ok(None);
+        }
+        let pid = match self.pid() {
+            Ok(pid) => pid,
+            Err(err) if matches!(err.downcast_ref::<WmCtlError>(), Some(WmCtlError::PropertyNotFound(_))) => {
+                return Ok(None)
+            },
+            Err(err) => return Err(err),
+        };
+        let pid = app_root_pid(pid, proc_stat);
+        debug!("root_pid: id: {}, pid: {:?}", self.id, pid);
+        Ok(pid)
+    }
+
+    /// Get the timestamp of the last user interaction with the window
+    /// * Returns None if the window doesn't define a user time
+    /// * A value of zero means the window should not be focused when mapped
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let time = win.user_time().unwrap();
+    /// ```
+    pub fn user_time(&self) -> WmCtlResult<Option<u32>> {
+        self.wm().window_user_time(self.id)
+    }
+
+    /// Get the window's opacity in the range 0.0..=1.0 as set for the compositing manager
+    /// * Returns 1.0 i.e. fully opaque if the window doesn't define an opacity
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let opacity = win.opacity().unwrap();
+    /// ```
+    pub fn opacity(&self) -> WmCtlResult<f32> {
+        Ok(self.wm().window_opacity(self.id)?.map_or(1.0, |x| x as f32 / u32::MAX as f32))
+    }
+
+    /// Get the regions of the window that are fully opaque as set for the compositing manager
+    /// * Each region is an (x, y, w, h) rectangle with x, y relative to the window's top left
+    /// * Returns an empty list if the window doesn't define an opaque region
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let region = win.opaque_region().unwrap();
+    /// ```
+    pub fn opaque_region(&self) -> WmCtlResult<Vec<(i32, i32, u32, u32)>> {
+        self.wm().window_opaque_region(self.id)
     }
 
     /// Get window name
@@ -48,7 +233,37 @@ impl Window {
     /// let name = win.name().unwrap();
     /// ```
     pub fn name(&self) -> WmCtlResult<String> {
-        WM().read().unwrap().window_name(self.id)
+        self.wm().window_name(self.id)
+    }
+
+    /// Determine if the window can be resized i.e. its size hints don't fix its minimum and maximum
+    /// sizes to the same value as some dialogs do
+    /// * Windows without size hints are considered resizable
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let resizable = win.is_resizable().unwrap();
+    /// ```
+    pub fn is_resizable(&self) -> WmCtlResult<bool> {
+        Ok(match self.wm().window_size_hints(self.id)? {
+            Some(hints) => hints.min_size.is_none() || hints.min_size != hints.max_size,
+            None => true,
+        })
+    }
+
+    /// Get window icon name i.e. the name shown when the window is iconified
+    /// * Falls back on the ICCCM WM_ICON_NAME if the EWMH name isn't set
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let icon_name = win.icon_name().unwrap();
+    /// ```
+    pub fn icon_name(&self) -> WmCtlResult<String> {
+        self.wm().window_icon_name(self.id)
     }
 
     /// Get window class which is typically the the application's name
@@ -60,7 +275,19 @@ impl Window {
     /// let class = win.class().unwrap();
     /// ```
     pub fn class(&self) -> WmCtlResult<String> {
-        WM().read().unwrap().window_class(self.id)
+        Ok(self.wm_class()?.1)
+    }
+
+    /// Get window instance and class as reported by the WM_CLASS property
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let (instance, class) = win.wm_class().unwrap();
+    /// ```
+    pub fn wm_class(&self) -> WmCtlResult<(String, String)> {
+        self.wm().window_wm_class(self.id)
     }
 
     /// Get window kind
@@ -75,7 +302,80 @@ impl Window {
     /// let kind = win.kind().unwrap();
     /// ```
     pub fn kind(&self) -> WmCtlResult<Kind> {
-        WM().read().unwrap().window_kind(self.id)
+        self.wm().window_kind(self.id)
+    }
+
+    /// Get all window kinds in the window's preferred order skipping unsupported types
+    /// * Defaults to Normal if the window doesn't set a type as per the EWMH spec
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let kinds = win.kinds().unwrap();
+    /// ```
+    pub fn kinds(&self) -> WmCtlResult<Vec<Kind>> {
+        self.wm().window_kinds(self.id)
+    }
+
+    /// Check if the window is a normal top level window. Windows without a type are normal as per the EWMH spec
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let normal = win.is_normal().unwrap();
+    /// ```
+    pub fn is_normal(&self) -> WmCtlResult<bool> {
+        Ok(self.kind()? == Kind::Normal)
+    }
+
+    /// Check if the window is a dialog
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let dialog = win.is_dialog().unwrap();
+    /// ```
+    pub fn is_dialog(&self) -> WmCtlResult<bool> {
+        Ok(self.kind()? == Kind::Dialog)
+    }
+
+    /// Check if the window is a dock or panel
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let dock = win.is_dock().unwrap();
+    /// ```
+    pub fn is_dock(&self) -> WmCtlResult<bool> {
+        Ok(self.kind()? == Kind::Dock)
+    }
+
+    /// Check if the window is the desktop e.g. a file manager drawing the desktop icons
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let desktop = win.is_desktop().unwrap();
+    /// ```
+    pub fn is_desktop(&self) -> WmCtlResult<bool> {
+        Ok(self.kind()? == Kind::Desktop)
+    }
+
+    /// Check if the window is a utility window e.g. a palette or toolbox
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let utility = win.is_utility().unwrap();
+    /// ```
+    pub fn is_utility(&self) -> WmCtlResult<bool> {
+        Ok(self.kind()? == Kind::Utility)
     }
 
     /// Get window state
@@ -87,7 +387,107 @@ impl Window {
     /// let state = win.state().unwrap();
     /// ```
     pub fn state(&self) -> WmCtlResult<Vec<State>> {
-        WM().read().unwrap().window_state(self.id)
+        self.wm().window_state(self.id)
+    }
+
+    /// Check if the window has the given state
+    ///
+    /// ### Arguments
+    /// * `state` - state to check for
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.has_state(State::Fullscreen).unwrap();
+    /// ```
+    pub fn has_state(&self, state: State) -> WmCtlResult<bool> {
+        Ok(self.state()?.contains(&state))
+    }
+
+    /// Check if the window is shown above other windows
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.is_above().unwrap();
+    /// ```
+    pub fn is_above(&self) -> WmCtlResult<bool> {
+        self.has_state(State::Above)
+    }
+
+    /// Check if the window is shown below other windows
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.is_below().unwrap();
+    /// ```
+    pub fn is_below(&self) -> WmCtlResult<bool> {
+        self.has_state(State::Below)
+    }
+
+    /// Check if the window is shown on all desktops
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.is_sticky().unwrap();
+    /// ```
+    pub fn is_sticky(&self) -> WmCtlResult<bool> {
+        self.has_state(State::Sticky)
+    }
+
+    /// Check if the window is rolled up to its titlebar
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.is_shaded().unwrap();
+    /// ```
+    pub fn is_shaded(&self) -> WmCtlResult<bool> {
+        self.has_state(State::Shaded)
+    }
+
+    /// Check if the window is a modal dialog
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.is_modal().unwrap();
+    /// ```
+    pub fn is_modal(&self) -> WmCtlResult<bool> {
+        self.has_state(State::Modal)
+    }
+
+    /// Check if the window has input focus
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.is_focused().unwrap();
+    /// ```
+    pub fn is_focused(&self) -> WmCtlResult<bool> {
+        self.has_state(State::Focused)
+    }
+
+    /// Get the names of window states that aren't part of the EWMH spec and thus not included in
+    /// state() e.g. _KDE_NET_WM_STATE_SKIP_SWITCHER
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let states = win.state_raw().unwrap();
+    /// ```
+    pub fn state_raw(&self) -> WmCtlResult<Vec<String>> {
+        self.wm().window_state_raw(self.id)
     }
 
     /// Get window parent
@@ -99,7 +499,15 @@ impl Window {
     /// let parent = win.parent().unwrap();
     /// ```
     pub fn parent(&self) -> WmCtlResult<Window> {
-        WM().read().unwrap().window_parent(self.id)
+        let id = self.wm().window_parent(self.id)?;
+        Ok(self.sibling(id))
+    }
+
+    /// Create a window for the given id that operates against the same connection as this window
+    fn sibling(&self, id: u32) -> Window {
+        let mut win = Window::new(id);
+        win.wm = self.wm.clone();
+        win
     }
 
     /// Get window desktop
@@ -111,144 +519,1369 @@ impl Window {
     /// let desktop = win.desktop().unwrap();
     /// ```
     pub fn desktop(&self) -> WmCtlResult<i32> {
-        WM().read().unwrap().window_desktop(self.id)
+        self.wm().window_desktop(self.id)
     }
 
-    /// Get window geometry
+    /// Get window desktop or None if the window is sticky or not assigned to a desktop
+    /// * Returns non zero based desktop number
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let win = window(12345);
-    /// let (x, y, w, h) = win.geometry().unwrap();
+    /// let desktop = win.desktop_opt().unwrap();
     /// ```
-    pub fn geometry(&self) -> WmCtlResult<(i32, i32, u32, u32)> {
-        WM().read().unwrap().window_geometry(self.id)
+    pub fn desktop_opt(&self) -> WmCtlResult<Option<u32>> {
+        let desktop = self.desktop()?;
+        if desktop == -1 || self.state()?.contains(&State::Sticky) {
+            return Ok(None);
+        }
+        Ok(Some(desktop as u32))
     }
 
-    /// Get visual window geometry
+    /// Get the name of the window's desktop or None if the window is sticky, not assigned to a
+    /// desktop or the window manager doesn't name the desktop
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let win = window(12345);
-    /// let (x, y, w, h) = win.visual_geometry().unwrap();
+    /// let name = win.desktop_name().unwrap();
     /// ```
-    pub fn visual_geometry(&self) -> WmCtlResult<(i32, i32, u32, u32)> {
-        WM().read().unwrap().window_visual_geometry(self.id)
+    pub fn desktop_name(&self) -> WmCtlResult<Option<String>> {
+        Ok(match self.desktop_opt()? {
+            Some(desktop) => self.wm().desktop_names()?.get(desktop as usize - 1).cloned(),
+            None => None,
+        })
     }
 
-    /// Get window frame border values added by the window manager
+    /// Determine if the window is shown on the active desktop
+    /// * Sticky windows and windows not assigned to a desktop are shown on all desktops
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let win = window(12345);
-    /// let (l, r, t, b) = win.borders();
+    /// let result = win.is_on_active_desktop().unwrap();
     /// ```
-    pub fn borders(&self) -> Border {
-        WM().read().unwrap().window_borders(self.id).unwrap_or(Border::default())
+    pub fn is_on_active_desktop(&self) -> WmCtlResult<bool> {
+        Ok(match self.desktop_opt()? {
+            Some(desktop) => desktop == self.wm().current_desktop()?,
+            None => true,
+        })
     }
 
-    /// Determine if this window is a GTK application
+    /// Move the window to the given desktop
+    ///
+    /// ### Arguments
+    /// * `desktop` - non zero based desktop number to move the window to
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let win = window(12345);
-    /// let result = win.is_gtk();
+    /// win.move_to_desktop(2).unwrap();
     /// ```
-    pub fn is_gtk(&self) -> bool {
-        WM().read().unwrap().window_is_gtk(self.id)
+    pub fn move_to_desktop(&self, desktop: u32) -> WmCtlResult<()> {
+        let wm = self.wm();
+        if desktop == 0 || desktop > wm.desktops()? {
+            return Err(WmCtlError::DesktopNotFound(desktop).into());
+        }
+        wm.set_window_desktop(self.id, desktop - 1)
     }
 
-    /// Get window GNOME border values added by GTK
+    /// Move the window to the given desktop, switch to that desktop and activate the window i.e.
+    /// take the window along to the desktop
+    /// * The desktop is switched before activating the window as some window managers otherwise
+    ///   ignore the activation or switch back to the window's previous desktop
+    ///
+    /// ### Arguments
+    /// * `desktop` - non zero based desktop number to move the window and switch to
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let win = window(12345);
-    /// let (l, r, t, b) = win.gtk_borders();
+    /// win.move_to_desktop_and_follow(2).unwrap();
     /// ```
-    pub fn gtk_borders(&self) -> Border {
-        WM().read().unwrap().window_gtk_borders(self.id).unwrap_or(Border::default())
+    pub fn move_to_desktop_and_follow(&self, desktop: u32) -> WmCtlResult<()> {
+        self.move_to_desktop(desktop)?;
+        let wm = self.wm();
+        wm.switch_desktop(desktop - 1)?;
+
+        // Wait for the window manager to switch desktops before activating the window
+        for _ in 0..SETTLE_ATTEMPTS {
+            if wm.current_desktop()? == desktop {
+                break;
+            }
+            std::thread::sleep(SETTLE_INTERVAL);
+        }
+        debug!("move_to_desktop_and_follow: id: {}, desktop: {}", self.id, desktop);
+        wm.activate_window(self.id)
     }
 
-    /// Get window mapped state
-    /// * doesn't return a valid state if all windows are included rather than just the managed ones
+    /// Move the window to all desktops by setting its desktop to the special all desktops value
+    /// * Some window managers key stickiness off this value rather than _NET_WM_STATE_STICKY and
+    ///   honor one but not the other so both `move_to_all_desktops` and `pin` are available
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let win = window(12345);
-    /// let state = win.mapped().unwrap();
+    /// win.move_to_all_desktops().unwrap();
     /// ```
-    pub fn mapped(&self) -> WmCtlResult<MapState> {
-        WM().read().unwrap().window_attributes(self.id)
+    pub fn move_to_all_desktops(&self) -> WmCtlResult<()> {
+        self.wm().set_window_desktop(self.id, ALL_DESKTOPS)
     }
 
-    /// Get all window properties generically
+    /// Get window geometry
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let win = window(12345);
-    /// win.properties().unwrap();
+    /// let (x, y, w, h) = win.geometry().unwrap();
     /// ```
-    pub fn properties(&self) -> WmCtlResult<Vec<Property>> {
-        WM().read().unwrap().window_properties(self.id)
+    pub fn geometry(&self) -> WmCtlResult<(i32, i32, u32, u32)> {
+        self.wm().window_geometry(self.id)
     }
 
-    /// Map the window to the screen
-    /// * Windows are created in the unmapped state and must be mapped to be visible
-    /// * Unmapping the window will have the opposite effect of hidding the window
-    /// * Useful for new windows or dialogs that need to conditionally be visible
-    /// * It is much faster to hide and show and window rather than recreate it
+    /// Get visual window geometry
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let win = window(12345);
-    /// win.map().unwrap();
+    /// let (x, y, w, h) = win.visual_geometry().unwrap();
     /// ```
-    pub fn map(&self) -> WmCtlResult<()> {
-        WM().read().unwrap().map_window(self.id)
+    pub fn visual_geometry(&self) -> WmCtlResult<(i32, i32, u32, u32)> {
+        self.wm().window_visual_geometry(self.id)
+    }
+
+    /// Get both the raw and visual window geometry in a single call avoiding fetching the window's
+    /// geometry twice when both are needed e.g. to report on the window
+    ///
+    /// ### Returns
+    /// * `(raw, visual)` as geometry() and visual_geometry() would return them
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let (raw, visual) = win.geometries().unwrap();
+    /// ```
+    pub fn geometries(&self) -> WmCtlResult<(Geometry, Geometry)> {
+        self.wm().window_geometries(self.id)
+    }
+
+    /// Get the work area of the monitor the window is currently on
+    /// * Falls back on the window manager's work area if RandR isn't available
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let area = win.work_area().unwrap();
+    /// ```
+    pub fn work_area(&self) -> WmCtlResult<Geometry> {
+        self.wm().window_work_area(self.id)
+    }
+
+    /// Get window frame border values added by the window manager
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let (l, r, t, b) = win.borders();
+    /// ```
+    pub fn borders(&self) -> Border {
+        self.wm().window_borders(self.id).unwrap_or(Border::default())
+    }
+
+    /// Determine if this window is a GTK application
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.is_gtk();
+    /// ```
+    pub fn is_gtk(&self) -> bool {
+        self.wm().window_is_gtk(self.id)
+    }
+
+    /// Get window GNOME border values added by GTK
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let (l, r, t, b) = win.gtk_borders();
+    /// ```
+    pub fn gtk_borders(&self) -> Border {
+        self.wm().window_gtk_borders(self.id).unwrap_or(Border::default())
+    }
+
+    /// Get window mapped state as tracked by the X server
+    /// * Valid for any window including those listed with `windows(true)` that aren't managed
+    /// * Managed windows are typically reparented into a frame by the window manager so the frame's
+    ///   state determines visibility e.g. a minimized window may still report Unviewable rather than
+    ///   Unmapped depending on whether the window manager unmaps the client or just its frame
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let state = win.mapped().unwrap();
+    /// ```
+    pub fn mapped(&self) -> WmCtlResult<MapState> {
+        self.wm().window_attributes(self.id)
+    }
+
+    /// Determine if the window is actually visible to the user. Unlike mapped() this takes into
+    /// account the window's minimized state and desktop.
+    /// * Window must be viewable i.e. it and all its ancestors are mapped
+    /// * Window must not be hidden i.e. minimized
+    /// * Window must be on the active desktop or sticky
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.is_visible().unwrap();
+    /// ```
+    pub fn is_visible(&self) -> WmCtlResult<bool> {
+        if self.mapped()? != MapState::Viewable || self.state()?.contains(&State::Hidden) {
+            return Ok(false);
+        }
+        self.is_on_active_desktop()
+    }
+
+    /// Get the X window class of the window i.e. InputOutput or InputOnly. InputOnly windows are
+    /// invisible and never managed by the window manager.
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let class = win.window_class_type().unwrap();
+    /// ```
+    pub fn window_class_type(&self) -> WmCtlResult<WinClass> {
+        self.wm().window_class_type(self.id)
+    }
+
+    /// Determine if the window has override redirect set. Override redirect windows e.g. menus,
+    /// tooltips and some game overlays bypass the window manager and can't be placed.
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let result = win.is_override_redirect().unwrap();
+    /// ```
+    pub fn is_override_redirect(&self) -> WmCtlResult<bool> {
+        self.wm().window_is_override_redirect(self.id)
+    }
+
+    /// Get all window properties generically with their decoded values sorted by name
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.properties().unwrap();
+    /// ```
+    pub fn properties(&self) -> WmCtlResult<Vec<Property>> {
+        self.wm().window_properties(self.id)
+    }
+
+    /// Get the names of all properties the window defines sorted by name
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let names = win.property_names().unwrap();
+    /// ```
+    pub fn property_names(&self) -> WmCtlResult<Vec<String>> {
+        Ok(self.properties()?.into_iter().map(|x| x.name).collect())
+    }
+
+    /// Get the named CARDINAL property's first value
+    /// * Returns None if the window doesn't define the property
+    ///
+    /// ### Arguments
+    /// * `name` - name of the property e.g. _NET_WM_PID
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let pid = win.get_cardinal("_NET_WM_PID").unwrap();
+    /// ```
+    pub fn get_cardinal(&self, name: &str) -> WmCtlResult<Option<u32>> {
+        Ok(self.get_cardinals(name)?.and_then(|x| x.first().copied()))
+    }
+
+    /// Get the named CARDINAL property's values
+    /// * Returns None if the window doesn't define the property
+    ///
+    /// ### Arguments
+    /// * `name` - name of the property e.g. _NET_FRAME_EXTENTS
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let extents = win.get_cardinals("_NET_FRAME_EXTENTS").unwrap();
+    /// ```
+    pub fn get_cardinals(&self, name: &str) -> WmCtlResult<Option<Vec<u32>>> {
+        self.wm().window_property32(self.id, name, AtomEnum::CARDINAL)
+    }
+
+    /// Get the named ATOM property's first value resolved to its name
+    /// * Returns None if the window doesn't define the property
+    ///
+    /// ### Arguments
+    /// * `name` - name of the property e.g. _NET_WM_WINDOW_TYPE
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let kind = win.get_atom("_NET_WM_WINDOW_TYPE").unwrap();
+    /// ```
+    pub fn get_atom(&self, name: &str) -> WmCtlResult<Option<String>> {
+        let wm = self.wm();
+        match wm.window_property32(self.id, name, AtomEnum::ATOM)?.and_then(|x| x.first().copied()) {
+            Some(atom) => Ok(Some(wm.atom_name(atom)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the named STRING or UTF8_STRING property's value
+    /// * Returns None if the window doesn't define the property
+    /// * STRING values are decoded as Latin-1 as per the ICCCM
+    ///
+    /// ### Arguments
+    /// * `name` - name of the property e.g. _NET_WM_NAME
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let name = win.get_string("_NET_WM_NAME").unwrap();
+    /// ```
+    pub fn get_string(&self, name: &str) -> WmCtlResult<Option<String>> {
+        self.wm().window_property_string(self.id, name)
+    }
+
+    /// Get the named WINDOW property's first value
+    /// * Returns None if the window doesn't define the property
+    ///
+    /// ### Arguments
+    /// * `name` - name of the property e.g. WM_TRANSIENT_FOR
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let owner = win.get_window("WM_TRANSIENT_FOR").unwrap();
+    /// ```
+    pub fn get_window(&self, name: &str) -> WmCtlResult<Option<u32>> {
+        let values = self.wm().window_property32(self.id, name, AtomEnum::WINDOW)?;
+        Ok(values.and_then(|x| x.first().copied()))
+    }
+
+    /// Wait until the named property is set or changed e.g. to wait for _NET_FRAME_EXTENTS to be
+    /// populated after mapping or for _NET_WM_STATE to reflect a requested change
+    ///
+    /// ### Arguments
+    /// * `atom_name` - name of the property to wait for
+    /// * `timeout` - how long to wait before giving up
+    ///
+    /// ### Returns
+    /// * true if the property was set or changed, false if the timeout expired first
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use std::time::Duration;
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.maximize().unwrap();
+    /// win.wait_for_property("_NET_WM_STATE", Duration::from_millis(500)).unwrap();
+    /// ```
+    pub fn wait_for_property(&self, atom_name: &str, timeout: Duration) -> WmCtlResult<bool> {
+        let watch = self.wm().watch_window_property(self.id, atom_name)?;
+        watch.wait(timeout)
+    }
+
+    /// Wait until the window's title changes e.g. for a browser tab or terminal title to update
+    /// * Watches _NET_WM_VISIBLE_NAME, _NET_WM_NAME and WM_NAME returning the title as name() does
+    ///
+    /// ### Arguments
+    /// * `timeout` - how long to wait before giving up
+    ///
+    /// ### Returns
+    /// * the new title or None if the timeout expired first
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use std::time::Duration;
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let title = win.wait_for_title_change(Duration::from_secs(5)).unwrap();
+    /// ```
+    pub fn wait_for_title_change(&self, timeout: Duration) -> WmCtlResult<Option<String>> {
+        let watch = self.wm().watch_window_title(self.id)?;
+        match watch.wait(timeout)? {
+            true => Ok(Some(self.name()?)),
+            false => Ok(None),
+        }
+    }
+
+    /// Capture the visible portion of the window as an image e.g. for building thumbnails
+    /// * Returns a `NotViewable` error if the window isn't viewable or is entirely off screen
+    /// * Portions of the window that are off screen are clipped
+    /// * Enable the `png` feature to save the image with `Image::save_png`
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let image = win.capture().unwrap();
+    /// ```
+    pub fn capture(&self) -> WmCtlResult<Image> {
+        self.wm().window_capture(self.id)
+    }
+
+    /// Map the window to the screen
+    /// * Windows are created in the unmapped state and must be mapped to be visible
+    /// * Unmapping the window will have the opposite effect of hidding the window
+    /// * Useful for new windows or dialogs that need to conditionally be visible
+    /// * It is much faster to hide and show and window rather than recreate it
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.map().unwrap();
+    /// ```
+    pub fn map(&self) -> WmCtlResult<()> {
+        self.wm().map_window(self.id)
+    }
+
+    /// Show the window's titlebar menu at the given position e.g. for custom titlebar replacements
+    /// * Only supported for GTK client side decorated windows
+    ///
+    /// ### Arguments
+    /// * `x` - x screen coordinate to show the menu at
+    /// * `y` - y screen coordinate to show the menu at
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.show_window_menu(100, 100).unwrap();
+    /// ```
+    pub fn show_window_menu(&self, x: i32, y: i32) -> WmCtlResult<()> {
+        self.wm().show_window_menu(self.id, x, y)
+    }
+
+    /// Check if the window has window manager decorations according to its Motif hints
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let decorated = win.is_decorated().unwrap();
+    /// ```
+    pub fn is_decorated(&self) -> WmCtlResult<bool> {
+        Ok(self.wm().window_motif_hints(self.id)?.decorated())
+    }
+
+    /// Add or remove the window manager's titlebar and border decorations via the Motif hints
+    /// * Not all window managers honor the Motif hints
+    ///
+    /// ### Arguments
+    /// * `decorated` - true to show the decorations, false to remove them
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.set_decorated(false).unwrap();
+    /// ```
+    pub fn set_decorated(&self, decorated: bool) -> WmCtlResult<()> {
+        let wm = self.wm();
+        let mut hints = wm.window_motif_hints(self.id)?;
+        hints.set_decorated(decorated);
+        wm.set_window_motif_hints(self.id, &hints)
+    }
+
+    /// Set the X server border width of the window e.g. to highlight the focused window
+    /// * This is the core X border not the window manager's decorations
+    /// * Reparenting window managers typically ignore or override it as the client window is
+    ///   inside the frame window
+    ///
+    /// ### Arguments
+    /// * `width` - border width in pixels
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.set_border_width(2).unwrap();
+    /// ```
+    pub fn set_border_width(&self, width: u32) -> WmCtlResult<()> {
+        self.wm().set_window_border_width(self.id, width)
+    }
+
+    /// Maximize the window both horizontally and vertically
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.maximize().unwrap();
+    /// ```
+    pub fn maximize(&self) -> WmCtlResult<()> {
+        self.invalidate();
+        self.wm().maximize_window(self.id)
+    }
+
+    /// Check if the window has a horizontally or vertically maximized
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.maximized()
+    /// ```
+    pub fn maximized(&self) -> bool {
+        self.state().is_ok_and(|states| states.contains(&State::MaxVert) || states.contains(&State::MaxHorz))
+    }
+
+    /// Remove the MaxVert and MaxHorz states
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.unmaximize().unwrap();
+    /// ```
+    pub fn unmaximize(&self) -> WmCtlResult<()> {
+        self.invalidate();
+        self.wm().unmaximize_window(self.id)
+    }
+
+    /// Maximize the window both horizontally and vertically then wait for the window manager to
+    /// apply the state e.g. before reading the window's maximized geometry
+    ///
+    /// ### Returns
+    /// * true if the window is maximized, false if the window manager didn't apply it in time
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// if win.maximize_verified().unwrap() {
+    ///     let (x, y, w, h) = win.geometry().unwrap();
+    /// }
+    /// ```
+    pub fn maximize_verified(&self) -> WmCtlResult<bool> {
+        self.maximize()?;
+        self.wait_for_state(|win| win.maximized())
+    }
+
+    /// Remove the MaxVert and MaxHorz states then wait for the window manager to apply the change
+    ///
+    /// ### Returns
+    /// * true if the window is unmaximized, false if the window manager didn't apply it in time
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.unmaximize_verified().unwrap();
+    /// ```
+    pub fn unmaximize_verified(&self) -> WmCtlResult<bool> {
+        self.unmaximize()?;
+        self.wait_for_state(|win| !win.maximized())
+    }
+
+    // Wait for _NET_WM_STATE changes until the given check passes or the timeout expires. The watch
+    // starts before the first check so that no change can be missed in between.
+    fn wait_for_state(&self, check: impl Fn(&Self) -> bool) -> WmCtlResult<bool> {
+        let deadline = Instant::now() + STATE_TIMEOUT;
+        let watch = self.wm().watch_window_property(self.id, "_NET_WM_STATE")?;
+        let mut applied = check(self);
+        while !applied && Instant::now() < deadline {
+            watch.wait(deadline.saturating_duration_since(Instant::now()))?;
+            applied = check(self);
+        }
+        debug!("wait_for_state: id: {}, applied: {}", self.id, applied);
+        Ok(applied)
+    }
+
+    /// Toggle the window between maximized and unmaximized
+    /// * Windows maximized on only one axis are unmaximized on both rather than toggling each state
+    ///   independently which would swap the maximized axis
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.toggle_maximize().unwrap();
+    /// ```
+    pub fn toggle_maximize(&self) -> WmCtlResult<()> {
+        let maximized = self.maximized();
+        self.invalidate();
+        match maximized {
+            true => self.wm().unmaximize_window(self.id),
+            false => self.wm().maximize_window(self.id),
+        }
+    }
+
+    /// Maximize the window to the work area of the monitor it is currently on by resizing it
+    /// explicitly rather than setting the maximized states which some window managers apply to the
+    /// whole X screen spanning all monitors.
+    /// * The monitor is the one containing the window's center
+    /// * Falls back on the window manager's work area if RandR isn't available
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.maximize_monitor().unwrap();
+    /// ```
+    pub fn maximize_monitor(&self) -> WmCtlResult<()> {
+        let work_area = self.wm().window_work_area(self.id)?;
+        debug!("maximize_monitor: {}, {}, {}, {}", work_area.x, work_area.y, work_area.w, work_area.h);
+        self.move_resize_visual(work_area.x, work_area.y, work_area.w, work_area.h)
+    }
+
+    /// Maximize the window to the work area of the monitor it is currently on less the given margin
+    /// on all sides such that the window doesn't touch the screen edges.
+    /// * The monitor is the one containing the window's center
+    /// * Falls back on the window manager's work area if RandR isn't available
+    ///
+    /// ### Arguments
+    /// * `margin` - space in pixels to leave between the window and each edge of the work area
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.maximize_with_margin(10).unwrap();
+    /// ```
+    pub fn maximize_with_margin(&self, margin: u32) -> WmCtlResult<()> {
+        let work_area = self.wm().window_work_area(self.id)?;
+        if margin >= work_area.w / 2 || margin >= work_area.h / 2 {
+            return Err(WmCtlError::InvalidMargin(margin).into());
+        }
+        let (x, y) = (work_area.x + margin as i32, work_area.y + margin as i32);
+        let (w, h) = (work_area.w - margin * 2, work_area.h - margin * 2);
+        debug!("maximize_with_margin: {}, {}, {}, {}", x, y, w, h);
+        self.move_resize_visual(x, y, w, h)
+    }
+
+    /// Toggle the named state on the window e.g. fullscreen, maximized_vert, above or sticky
+    /// * The request is sent even if the window manager doesn't advertise the state, see
+    ///   `libwmctl::check_state_supported()` to check first
+    ///
+    /// ### Arguments
+    /// * `state` - EWMH state name with or without the _NET_WM_STATE_ prefix
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.toggle("fullscreen").unwrap();
+    /// ```
+    pub fn toggle(&self, state: &str) -> WmCtlResult<()> {
+        let state = State::try_from(state)?;
+        self.wm().toggle_window_state(self.id, &state)
+    }
+
+    /// Add the named state to the window e.g. fullscreen, maximized_vert, above or sticky
+    /// * The request is sent even if the window manager doesn't advertise the state, see
+    ///   `libwmctl::check_state_supported()` to check first
+    ///
+    /// ### Arguments
+    /// * `state` - EWMH state name with or without the _NET_WM_STATE_ prefix
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.add_state("above").unwrap();
+    /// ```
+    pub fn add_state(&self, state: &str) -> WmCtlResult<()> {
+        let state = State::try_from(state)?;
+        self.wm().add_window_state(self.id, &state)
+    }
+
+    /// Remove the named state from the window e.g. fullscreen, maximized_vert, above or sticky
+    /// * The request is sent even if the window manager doesn't advertise the state, see
+    ///   `libwmctl::check_state_supported()` to check first
+    ///
+    /// ### Arguments
+    /// * `state` - EWMH state name with or without the _NET_WM_STATE_ prefix
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.remove_state("above").unwrap();
+    /// ```
+    pub fn remove_state(&self, state: &str) -> WmCtlResult<()> {
+        let state = State::try_from(state)?;
+        self.wm().remove_window_state(self.id, &state)
+    }
+
+    /// Flash the window's taskbar entry by toggling the DemandsAttention state on and off e.g. to
+    /// signal that a long running command finished. The attention state is cleared at the end.
+    /// * The visual effect depends on the window manager and taskbar, some ignore the state
+    ///
+    /// ### Arguments
+    /// * `times` - number of times to turn the attention state on and off
+    /// * `interval` - time to wait between each toggle
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.flash(3, Duration::from_millis(500)).unwrap();
+    /// ```
+    pub fn flash(&self, times: u32, interval: Duration) -> WmCtlResult<()> {
+        for _ in 0..times * 2 {
+            self.wm().toggle_window_state(self.id, &State::DemandsAttention)?;
+            std::thread::sleep(interval);
+        }
+        self.wm().remove_window_state(self.id, &State::DemandsAttention)
+    }
+
+    /// Activate the window i.e. ask the window manager to switch to its desktop, raise and focus it
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.activate().unwrap();
+    /// ```
+    pub fn activate(&self) -> WmCtlResult<()> {
+        self.wm().activate_window(self.id)
+    }
+
+    /// Raise the window above its siblings without changing the input focus
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.raise().unwrap();
+    /// ```
+    pub fn raise(&self) -> WmCtlResult<()> {
+        self.wm().raise_window(self.id)
+    }
+
+    /// Restack the window at the very top of the stack above all its siblings
+    /// * Alias of raise() which already restacks at the very top, provided to pair with to_bottom()
+    /// * This is a one time restack the window manager or user may later change unlike the Above
+    ///   state set via toggle("above") which persistently keeps the window above normal windows
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.to_top().unwrap();
+    /// ```
+    pub fn to_top(&self) -> WmCtlResult<()> {
+        self.raise()
+    }
+
+    /// Restack the window at the very bottom of the stack below all its siblings e.g. to send a
+    /// desktop widget to the back
+    /// * This is a one time restack the window manager or user may later change unlike the Below
+    ///   state set via toggle("below") which persistently keeps the window below normal windows
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.to_bottom().unwrap();
+    /// ```
+    pub fn to_bottom(&self) -> WmCtlResult<()> {
+        self.wm().lower_window(self.id)
+    }
+
+    /// Stack the window directly above the given sibling window
+    /// * Both windows must share the same parent e.g. both top level or both managed by the same
+    ///   reparenting window manager which receives the request to honor it
+    ///
+    /// ### Arguments
+    /// * `sibling` - window to stack above
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.stack_above(&window(67890)).unwrap();
+    /// ```
+    pub fn stack_above(&self, sibling: &Window) -> WmCtlResult<()> {
+        self.wm().restack_window(self.id, sibling.id, StackMode::ABOVE)
+    }
+
+    /// Stack the window directly below the given sibling window
+    /// * Both windows must share the same parent e.g. both top level or both managed by the same
+    ///   reparenting window manager which receives the request to honor it
+    ///
+    /// ### Arguments
+    /// * `sibling` - window to stack below
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.stack_below(&window(67890)).unwrap();
+    /// ```
+    pub fn stack_below(&self, sibling: &Window) -> WmCtlResult<()> {
+        self.wm().restack_window(self.id, sibling.id, StackMode::BELOW)
+    }
+
+    /// Raise the window above its siblings and give it the input focus in a single call to avoid
+    /// racing the window manager between the two requests.
+    /// * Prefer this for immediate "bring to front" actions on windows on the current desktop
+    /// * Prefer activate() when the window may be on another desktop or minimized as only the
+    ///   window manager can switch desktops and restore windows, though some animate or defer it
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.raise_and_focus().unwrap();
+    /// ```
+    pub fn raise_and_focus(&self) -> WmCtlResult<()> {
+        self.wm().raise_and_focus_window(self.id)
+    }
+
+    /// Hide the window from or show it in the taskbar via the SkipTaskbar state
+    ///
+    /// ### Arguments
+    /// * `enable` - true to hide the window from the taskbar, false to show it
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.set_skip_taskbar(true).unwrap();
+    /// ```
+    pub fn set_skip_taskbar(&self, enable: bool) -> WmCtlResult<()> {
+        self.wm().set_window_skip_taskbar(self.id, enable)
+    }
+
+    /// Hide the window from or show it in the pager via the SkipPager state
+    ///
+    /// ### Arguments
+    /// * `enable` - true to hide the window from the pager, false to show it
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.set_skip_pager(true).unwrap();
+    /// ```
+    pub fn set_skip_pager(&self, enable: bool) -> WmCtlResult<()> {
+        self.wm().set_window_skip_pager(self.id, enable)
+    }
+
+    /// Hand off an interactive move of the window to the window manager starting at the current
+    /// pointer position e.g. when the user drags a custom titlebar
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.begin_move().unwrap();
+    /// ```
+    pub fn begin_move(&self) -> WmCtlResult<()> {
+        self.wm().move_resize_interactive(self.id, ResizeEdge::Move)
+    }
+
+    /// Hand off an interactive resize of the window to the window manager starting at the current
+    /// pointer position e.g. when the user drags a custom window edge
+    ///
+    /// ### Arguments
+    /// * `edge` - edge or corner being dragged or one of the keyboard or cancel directions
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.begin_resize(ResizeEdge::BottomRight).unwrap();
+    /// ```
+    pub fn begin_resize(&self, edge: ResizeEdge) -> WmCtlResult<()> {
+        self.wm().move_resize_interactive(self.id, edge)
+    }
+
+    /// Pin the window i.e. show it on all desktops and above other windows
+    /// * Adds the Sticky and Above states in a single request
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.pin().unwrap();
+    /// ```
+    pub fn pin(&self) -> WmCtlResult<()> {
+        self.wm().pin_window(self.id)
+    }
+
+    /// Check if the window is pinned i.e. has both the Sticky and Above states
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.is_pinned().unwrap();
+    /// ```
+    pub fn is_pinned(&self) -> WmCtlResult<bool> {
+        let states = self.state()?;
+        Ok(states.contains(&State::Sticky) && states.contains(&State::Above))
+    }
+
+    /// Unpin the window i.e. remove the Sticky and Above states in a single request
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.unpin().unwrap();
+    /// ```
+    pub fn unpin(&self) -> WmCtlResult<()> {
+        self.wm().unpin_window(self.id)
+    }
+
+    /// Move and resize the window using raw coordinates as reported by geometry() i.e. without
+    /// window manager borders or client side decorations taken into account
+    ///
+    /// ### Arguments
+    /// * `x` - x coordinate of the window
+    /// * `y` - y coordinate of the window
+    /// * `w` - width of the window
+    /// * `h` - height of the window
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.move_resize(0, 0, 500, 500).unwrap();
+    /// ```
+    pub fn move_resize(&self, x: i32, y: i32, w: u32, h: u32) -> WmCtlResult<()> {
+        if self.maximized() {
+            self.unmaximize()?;
+        }
+        self.invalidate();
+        self.wm().move_resize_window(self.id, None, Some(x), Some(y), Some(w), Some(h))
+    }
+
+    /// Move and resize the window then wait for the window manager to apply the change and return
+    /// the geometry the window actually ended up with e.g. for scripts that need to confirm placement.
+    /// * Waits until the geometry is within tolerance of the request or the attempts run out
+    /// * Returns the geometry in the same raw coordinates as geometry()
+    ///
+    /// ### Arguments
+    /// * `x` - x coordinate to use for the window during positioning
+    /// * `y` - y coordinate to use for the window during positioning
+    /// * `w` - width to use for the window during positioning
+    /// * `h` - height to use for the window during positioning
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let g = win.move_resize_get(0, 0, 500, 500).unwrap();
+    /// ```
+    pub fn move_resize_get(&self, x: i32, y: i32, w: u32, h: u32) -> WmCtlResult<Geometry> {
+        self.move_resize(x, y, w, h)?;
+        self.placed.set(Some((Some(x), Some(y), Some(w), Some(h))));
+        for _ in 0..SETTLE_ATTEMPTS {
+            self.wm().sync()?;
+            if self.verify_placement()? {
+                break;
+            }
+            std::thread::sleep(SETTLE_INTERVAL);
+        }
+        let g = Geometry::from(self.geometry()?);
+        debug!("move_resize_get: {}, {}, {}, {}", g.x, g.y, g.w, g.h);
+        Ok(g)
+    }
+
+    /// Move and resize the window using visual coordinates as reported by visual_geometry() i.e.
+    /// the area the window visually occupies on screen including window manager borders and
+    /// excluding client side decorations.
+    ///
+    /// ### Arguments
+    /// * `x` - visual x coordinate of the window
+    /// * `y` - visual y coordinate of the window
+    /// * `w` - visual width of the window
+    /// * `h` - visual height of the window
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// let (x, y, w, h) = win.visual_geometry().unwrap();
+    /// win.move_resize_visual(x, y, w, h).unwrap();
+    /// ```
+    pub fn move_resize_visual(&self, x: i32, y: i32, w: u32, h: u32) -> WmCtlResult<()> {
+        let (x, y, w, h) = visual_to_raw(&Geometry::new(x, y, w, h), &self.borders(), &self.gtk_borders());
+        debug!("move_resize_visual: {}, {}, {}, {}", x, y, w, h);
+        self.move_resize(x, y, w, h)
+    }
+
+    /// Restore the window to the given geometry, desktop and states e.g. when restoring a saved
+    /// layout. Window managers ignore geometry changes to maximized and fullscreen windows so those
+    /// states are removed first, then the window is moved and resized, moved to the desktop and
+    /// finally the given states are added e.g. to re-maximize the window.
+    ///
+    /// ### Arguments
+    /// * `geometry` - visual geometry to move and resize the window to
+    /// * `desktop` - non zero based desktop number to move the window to or None to leave it
+    /// * `states` - states to add to the window once placed
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.apply(Geometry::new(0, 0, 800, 600), Some(2), &[State::Above]).unwrap();
+    /// ```
+    pub fn apply(&self, geometry: Geometry, desktop: Option<u32>, states: &[State]) -> WmCtlResult<()> {
+        let current = self.state()?;
+        if current.contains(&State::MaxVert) || current.contains(&State::MaxHorz) {
+            self.unmaximize()?;
+        }
+        if current.contains(&State::Fullscreen) {
+            self.wm().remove_window_state(self.id, &State::Fullscreen)?;
+        }
+
+        self.move_resize_visual(geometry.x, geometry.y, geometry.w, geometry.h)?;
+        if let Some(desktop) = desktop {
+            self.move_to_desktop(desktop)?;
+        }
+        for state in states.iter() {
+            self.wm().add_window_state(self.id, state)?;
+        }
+        Ok(())
+    }
+
+    /// Resize the window to the given fractions of the work area of the monitor it is currently on
+    /// keeping the window's top left corner where it is.
+    /// * The window is shifted as needed to keep it fully on the monitor
+    /// * Falls back on the window manager's work area if RandR isn't available
+    ///
+    /// ### Arguments
+    /// * `wfrac` - fraction of the monitor's work area width in the range 0.0 < wfrac <= 1.0
+    /// * `hfrac` - fraction of the monitor's work area height in the range 0.0 < hfrac <= 1.0
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.resize_to_monitor_fraction(0.5, 0.75).unwrap();
+    /// ```
+    pub fn resize_to_monitor_fraction(&self, wfrac: f32, hfrac: f32) -> WmCtlResult<()> {
+        self.resize_fraction(wfrac, hfrac, false)
+    }
+
+    /// Resize the window to the given fraction of the work area of the monitor it is currently on
+    /// on both axes and center it in a single operation e.g. a reading mode layout.
+    /// * Falls back on the window manager's work area if RandR isn't available
+    ///
+    /// ### Arguments
+    /// * `frac` - fraction of the monitor's work area in the range 0.1..=1.0
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.center_fraction(0.6).unwrap();
+    /// ```
+    pub fn center_fraction(&self, frac: f32) -> WmCtlResult<()> {
+        let frac = check_fraction(frac, CENTER_FRACTION_MIN)?;
+        self.resize_fraction(frac, frac, true)
+    }
+
+    // Resize the window to the given fractions of its monitor's work area either keeping its top
+    // left corner where it is, shifted as needed to stay on the monitor, or centering it
+    fn resize_fraction(&self, wfrac: f32, hfrac: f32, center: bool) -> WmCtlResult<()> {
+        let (wfrac, hfrac) = (check_fraction(wfrac, 0.0)?, check_fraction(hfrac, 0.0)?);
+
+        // Unmaximize to shape the window correctly
+        if self.maximized() {
+            self.unmaximize()?;
+        }
+
+        // Calculate the visual geometry within the work area
+        let border = self.borders();
+        let csd_border = self.gtk_borders();
+        let (x, y, _, _) = self.visual_geometry()?;
+        let wm = self.wm();
+        let work_area = wm.window_work_area(self.id)?;
+        let w = (work_area.w as f32 * wfrac) as u32;
+        let h = (work_area.h as f32 * hfrac) as u32;
+        let g = match center {
+            true => Geometry::new(
+                work_area.x + ((work_area.w - w) / 2) as i32,
+                work_area.y + ((work_area.h - h) / 2) as i32,
+                w,
+                h,
+            ),
+            false => Geometry::new(x, y, w, h).clamp(&work_area),
+        };
+
+        // Convert back from visual to raw values
+        let (x, y, w, h) = visual_to_raw(&g, &border, &csd_border);
+        debug!("resize_fraction: {}, {}, {}, {}, center: {}", x, y, w, h, center);
+        self.invalidate();
+        wm.move_resize_window(self.id, None, Some(x), Some(y), Some(w), Some(h))
+    }
+
+    /// Move the window to the given position in the large virtual desktop keeping its current size.
+    /// Window positions are relative to the current viewport so the viewport's offset is subtracted
+    /// e.g. for fvwm style setups where the virtual desktop is larger than the screen.
+    /// * Falls back on no offset if the window manager doesn't support viewports
+    ///
+    /// ### Arguments
+    /// * `x` - x coordinate of the window's visual top left in the virtual desktop
+    /// * `y` - y coordinate of the window's visual top left in the virtual desktop
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.move_to_viewport(1920, 0).unwrap();
+    /// ```
+    pub fn move_to_viewport(&self, x: i32, y: i32) -> WmCtlResult<()> {
+        let (vx, vy) = self.wm().viewport().unwrap_or_default();
+        let (_, _, w, h) = self.visual_geometry()?;
+        debug!("move_to_viewport: viewport: {}, {}, x: {}, y: {}", vx, vy, x, y);
+        self.move_resize_visual(x - vx, y - vy, w, h)
+    }
+
+    /// Move the window so that its center is under the pointer keeping its current size
+    /// * The window is shifted as needed to keep it fully on the monitor the pointer is on
+    /// * Falls back on the window manager's work area if RandR isn't available
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.move_to_pointer().unwrap();
+    /// ```
+    pub fn move_to_pointer(&self) -> WmCtlResult<()> {
+        // Unmaximize to position the window correctly
+        if self.maximized() {
+            self.unmaximize()?;
+        }
+
+        // Center the visual geometry on the pointer clamping to the work area
+        let csd_border = self.gtk_borders();
+        let (_, _, w, h) = self.visual_geometry()?;
+        let wm = self.wm();
+        let (px, py) = wm.pointer()?;
+        let work_area = wm.work_area_at(px, py)?;
+        let g = Geometry::new(px - (w / 2) as i32, py - (h / 2) as i32, w, h).clamp(&work_area);
+
+        // Convert back from visual to raw values
+        let (x, y, _, _) = visual_to_raw(&g, &Border::default(), &csd_border);
+        debug!("move_to_pointer: {}, {}", x, y);
+        self.invalidate();
+        wm.move_resize_window(self.id, None, Some(x), Some(y), None, None)
+    }
+
+    /// Move the window fully on screen if it is mostly off screen e.g. after a resolution change or
+    /// unplugging a monitor. The window is moved to the nearest monitor and shifted as needed to
+    /// fit within its work area.
+    /// * Windows with at least half of their area visible on a monitor are left alone
+    /// * Docks and desktop windows sit outside the work area by design and are left alone
+    /// * Falls back on the window manager's work area if RandR isn't available
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.ensure_on_screen().unwrap();
+    /// ```
+    pub fn ensure_on_screen(&self) -> WmCtlResult<()> {
+        if matches!(self.kind()?, Kind::Dock | Kind::Desktop) {
+            return Ok(());
+        }
+        let border = self.borders();
+        let csd_border = self.gtk_borders();
+        let g = Geometry::from(self.visual_geometry()?);
+        let wm = self.wm();
+        let mut areas = wm.monitors()?.into_iter().map(|x| (x.geometry, x.work_area)).collect::<Vec<_>>();
+        if areas.is_empty() {
+            let work_area = Geometry::new(wm.work_x, wm.work_y, wm.work_width, wm.work_height);
+            areas.push((wm.screen_geometry(), work_area));
+        }
+
+        // Move the window into the nearest work area if mostly off screen
+        if let Some(g) = rescue_geometry(&g, &areas) {
+            let (x, y, w, h) = visual_to_raw(&g, &border, &csd_border);
+            debug!("ensure_on_screen: {}, {}, {}, {}", x, y, w, h);
+            self.invalidate();
+            wm.move_resize_window(self.id, None, Some(x), Some(y), Some(w), Some(h))?;
+        }
+        Ok(())
+    }
+
+    /// Wait for the X server to process all pending requests and forget the geometry last requested
+    /// by place() such that verify_placement() has nothing stale to compare against.
+    /// * Window details are never cached so reads always reflect the window's current values
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.move_resize(0, 0, 500, 500).unwrap();
+    /// win.refresh().unwrap();
+    /// let (x, y, w, h) = win.geometry().unwrap();
+    /// ```
+    pub fn refresh(&self) -> WmCtlResult<()> {
+        self.invalidate();
+        self.wm().sync()
+    }
+
+    // Forget the geometry last requested by place() as the window is being changed by other means
+    fn invalidate(&self) {
+        self.placed.set(None);
     }
 
-    /// Maximize the window both horizontally and vertically
+    /// Verify that the window's geometry matches what was last requested by place(). Window managers
+    /// don't always honor placement requests precisely so this allows callers to react e.g. by
+    /// placing the window again.
+    /// * Only the values place() changed are compared and small differences are tolerated
+    /// * Returns true if place() hasn't been called yet as there is nothing to verify
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
-    /// let win = window(12345);
-    /// win.maximize().unwrap();
+    /// let win = window(12345).shape(Shape::Large).pos(Position::Right);
+    /// win.place().unwrap();
+    /// let matched = win.verify_placement().unwrap();
     /// ```
-    pub fn maximize(&self) -> WmCtlResult<()> {
-        WM().read().unwrap().maximize_window(self.id)
+    pub fn verify_placement(&self) -> WmCtlResult<bool> {
+        let Some(requested) = self.placed.get() else {
+            return Ok(true);
+        };
+        let g = Geometry::from(self.visual_geometry()?);
+        let actual = Geometry::from(visual_to_raw(&g, &self.borders(), &self.gtk_borders()));
+
+        // Values place() left unchanged always match
+        let (x, y, w, h) = requested;
+        let expected = Geometry::new(
+            x.unwrap_or(actual.x),
+            y.unwrap_or(actual.y),
+            w.unwrap_or(actual.w),
+            h.unwrap_or(actual.h),
+        );
+        let matched = expected.approx_eq(&actual, PLACEMENT_TOLERANCE);
+        debug!(id = self.id, ?requested, ?actual, matched, "verify_placement");
+        Ok(matched)
     }
 
-    /// Check if the window has a horizontally or vertically maximized
+    /// Check if the window's visual geometry matches the expected geometry within the given
+    /// tolerance e.g. to detect when the window manager has overridden or constrained a placement
+    /// by snapping it to a grid
+    /// * Unlike verify_placement() this works for placements made by any means
+    ///
+    /// ### Arguments
+    /// * `expected` - visual geometry the window is expected to have
+    /// * `tol` - number of pixels each value is allowed to differ by
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let win = window(12345);
-    /// win.maximized()
+    /// win.move_resize_visual(0, 0, 800, 600).unwrap();
+    /// let matched = win.placement_matches(&Geometry::new(0, 0, 800, 600), 2).unwrap();
     /// ```
-    pub fn maximized(&self) -> bool {
-        self.state().is_ok_and(|states| states.contains(&State::MaxVert) || states.contains(&State::MaxHorz))
+    pub fn placement_matches(&self, expected: &Geometry, tol: u32) -> WmCtlResult<bool> {
+        let actual = Geometry::from(self.visual_geometry()?);
+        let matched = actual.approx_eq(expected, tol);
+        debug!(id = self.id, ?expected, ?actual, tol, matched, "placement_matches");
+        Ok(matched)
     }
 
-    /// Remove the MaxVert and MaxHorz states
+    /// Swap the geometry and desktop of this window with the other window
+    /// * Both windows are read before either is changed to avoid using stale values
+    /// * Each window is clamped into the work area of its destination e.g. when moving between
+    ///   monitors of different sizes
+    ///
+    /// ### Arguments
+    /// * `other` - window to swap with
+    pub(crate) fn swap(&self, other: &Window) -> WmCtlResult<()> {
+        let (a, b) = (Geometry::from(self.visual_geometry()?), Geometry::from(other.visual_geometry()?));
+        let (a_desktop, b_desktop) = (self.desktop()?, other.desktop()?);
+        debug!(a = self.id, b = other.id, ?a, ?b, a_desktop, b_desktop, "swap");
+
+        // Swap desktops when both windows are assigned to a specific desktop
+        if a_desktop != b_desktop && a_desktop != -1 && b_desktop != -1 {
+            self.move_to_desktop(b_desktop as u32)?;
+            other.move_to_desktop(a_desktop as u32)?;
+        }
+
+        // Swap geometry clamping into the destination's work area
+        for (win, dest) in [(self, &b), (other, &a)] {
+            if win.maximized() {
+                win.unmaximize()?;
+            }
+            let (cx, cy) = dest.center();
+            let g = dest.clamp(&win.wm().work_area_at(cx, cy)?);
+            win.move_resize_visual(g.x, g.y, g.w, g.h)?;
+        }
+        Ok(())
+    }
+
+    /// Find the nearest visible normal window in the given direction from this window's center
+    /// * Only windows whose centers lie within a 90 degree cone facing the direction are considered
+    /// * Windows straight ahead are preferred over closer windows off to the side
+    ///
+    /// ### Arguments
+    /// * `direction` - one of Left, Right, Top or Bottom
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
-    /// let win = window(12345);
-    /// win.unmaximize().unwrap();
+    /// if let Some(win) = active().neighbor(Position::Right).unwrap() {
+    ///     win.activate().unwrap();
+    /// }
     /// ```
-    pub fn unmaximize(&self) -> WmCtlResult<()> {
-        WM().read().unwrap().unmaximize_window(self.id)
+    pub fn neighbor(&self, direction: Position) -> WmCtlResult<Option<Window>> {
+        if !matches!(direction, Position::Left | Position::Right | Position::Top | Position::Bottom) {
+            return Err(WmCtlError::InvalidWinPosition(direction.to_string()).into());
+        }
+        let from = Geometry::from(self.visual_geometry()?).center();
+
+        let ids = self.wm().windows(false)?;
+        let mut candidates = vec![];
+        for id in ids.into_iter().filter(|&x| x != self.id) {
+            let win = self.sibling(id);
+            if !win.kind().is_ok_and(|x| x == Kind::Normal) || !win.is_visible().unwrap_or(false) {
+                continue;
+            }
+            if let Ok(g) = win.visual_geometry() {
+                candidates.push((id, Geometry::from(g).center()));
+            }
+        }
+
+        let neighbor = nearest_in_direction(from, &direction, &candidates);
+        debug!(id = self.id, ?direction, ?neighbor, "neighbor");
+        Ok(neighbor.map(|x| self.sibling(x)))
     }
 
     /// Queue the shape the window should be. This will not take effect until the place() method is called.
@@ -282,8 +1915,62 @@ impl Window {
         self
     }
 
-    /// Move and resize the window according to the queued directives configured with the shape()
-    /// and pos() methods.
+    /// Queue the monitor the window should be placed on. This will not take effect until the place()
+    /// method is called. The monitor's work area will be used for shaping and positioning rather
+    /// than the monitor the window is currently on.
+    ///
+    /// ### Arguments
+    /// * `name` - name of the monitor to place the window on e.g. HDMI-1
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// window(12345).monitor("HDMI-1").shape(Shape::Large).pos(Position::Center).place().unwrap();
+    /// ```
+    pub fn monitor(mut self, name: &str) -> Self {
+        self.monitor = Some(name.to_owned());
+        self
+    }
+
+    /// Queue clamping the window to the work area of the monitor it is being placed on such that it
+    /// remains fully on screen. This will not take effect until the place() method is called.
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// window(12345).shape(Shape::Static(3000, 2000)).clamp().place().unwrap();
+    /// ```
+    pub fn clamp(mut self) -> Self {
+        self.clamp = true;
+        self
+    }
+
+    /// Queue subtracting the areas reserved by panels and other dock windows from the work area the
+    /// window is placed in. This is a workaround for window managers that report an incorrect
+    /// _NET_WORKAREA causing windows to overlap panels. This will not take effect until the place()
+    /// method is called.
+    ///
+    /// ### Arguments
+    /// * `enable` - true to respect the struts of dock windows
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// window(12345).pos(Position::Bottom).respect_struts(true).place().unwrap();
+    /// ```
+    pub fn respect_struts(mut self, enable: bool) -> Self {
+        self.struts = enable;
+        self
+    }
+
+    /// Move and resize the window according to the queued directives configured with the shape(),
+    /// pos(), monitor() and clamp() methods.
+    /// * Uses the work area of the queued monitor or the monitor the window is currently on
+    /// * Falls back on the window manager's work area if RandR isn't available
+    /// * Static positions are always absolute screen coordinates
+    /// * Override redirect windows are skipped as they bypass the window manager
+    /// * The Max shape maximizes the window ignoring any other directives
+    /// * Fixed size windows are only moved or return NotResizable if there is no position
     ///
     /// ### Examples
     /// ```ignore
@@ -292,10 +1979,66 @@ impl Window {
     /// win.shape(Shape::Large).pos(Position::Right).place();
     /// ```
     pub fn place(&self) -> WmCtlResult<()> {
-        if self.shape.is_none() && self.pos.is_none() {
+        self.place_with(self.shape.as_ref(), self.pos.as_ref())
+    }
+
+    /// Move the window to the given position immediately rather than queuing it with pos()
+    /// * Uses the work area of the queued monitor or the monitor the window is currently on
+    ///
+    /// ### Arguments
+    /// * `pos` - pre-defined position to move the window to
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.move_to(Position::Left).unwrap();
+    /// ```
+    pub fn move_to(&self, pos: Position) -> WmCtlResult<()> {
+        self.place_with(None, Some(&pos))
+    }
+
+    /// Shape the window immediately rather than queuing it with shape()
+    /// * Uses the work area of the queued monitor or the monitor the window is currently on
+    /// * Returns NotResizable for fixed size windows
+    ///
+    /// ### Arguments
+    /// * `shape` - pre-defined shape to manipulate the window into
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let win = window(12345);
+    /// win.reshape(Shape::Large).unwrap();
+    /// ```
+    pub fn reshape(&self, shape: Shape) -> WmCtlResult<()> {
+        self.place_with(Some(&shape), None)
+    }
+
+    /// Move and resize the window with the given shape and position rather than the queued ones
+    fn place_with(&self, shape: Option<&Shape>, pos: Option<&Position>) -> WmCtlResult<()> {
+        if shape.is_none() && pos.is_none() {
+            return Ok(());
+        }
+
+        // Override redirect windows bypass the window manager and can't be placed
+        if self.is_override_redirect()? {
+            debug!("place: skipping override redirect window: {}", self.id);
             return Ok(());
         }
-        let wm = WM().read().unwrap();
+
+        // Fixed size windows can only be moved so skip shaping them
+        let shape = match shape {
+            Some(_) if !self.is_resizable()? => {
+                if pos.is_none() {
+                    return Err(WmCtlError::NotResizable(self.id).into());
+                }
+                debug!("place: skipping shape for fixed size window: {}", self.id);
+                None
+            },
+            _ => shape,
+        };
+        let wm = self.wm();
 
         // Unmaximize to shape and position the window correctly
         if self.maximized() {
@@ -307,10 +2050,17 @@ impl Window {
         let csd_border = self.gtk_borders();
         let (_, _, w, h) = self.geometry()?;
         let mut size = Rect::new(w, h);
-        let area = Rect::new(wm.work_width, wm.work_height);
+        let mut work_area = match &self.monitor {
+            Some(name) => wm.monitor(name)?.work_area,
+            None => wm.window_work_area(self.id)?,
+        };
+        if self.struts {
+            work_area = subtract_struts(&work_area, &wm.struts()?);
+        }
+        let area = Rect::new(work_area.w, work_area.h);
 
         // Shape the window as directed
-        let (gravity, sw, sh) = if let Some(shape) = self.shape.as_ref() {
+        let (gravity, sw, sh) = if let Some(shape) = shape {
             let (gravity, sw, sh) = translate_shape(&size, &border, &csd_border, &area, shape)?;
 
             // Update size with translated changes for positioning
@@ -322,7 +2072,7 @@ impl Window {
             }
 
             // Don't use gravity if positioning is required
-            if self.pos.is_some() {
+            if pos.is_some() {
                 (None, sw, sh)
             } else {
                 (gravity, sw, sh)
@@ -331,17 +2081,263 @@ impl Window {
             (None, None, None)
         };
 
-        // Position the window if directed
-        let (x, y) = if let Some(pos) = &self.pos {
-            translate_pos(&size, &border, &csd_border, &area, pos)?
+        // Zero sizes are the sentinel for maximizing the window
+        if sw == Some(0) && sh == Some(0) {
+            debug!("place: maximizing window: {}", self.id);
+            return self.maximize();
+        }
+
+        // Position the window if directed offsetting into the work area
+        let (x, y) = match pos {
+            Some(pos @ Position::Static(..)) => translate_pos(&size, &border, &csd_border, &area, pos)?,
+            Some(pos) => {
+                let (x, y) = translate_pos(&size, &border, &csd_border, &area, pos)?;
+                (x.map(|x| x + work_area.x), y.map(|y| y + work_area.y))
+            },
+            None => (None, None),
+        };
+
+        // Clamp the resulting visual geometry into the work area if directed
+        let (x, y, sw, sh) = if self.clamp {
+            let (cx, cy, cw, ch) = visual_to_raw(&Geometry::from(self.visual_geometry()?), &border, &csd_border);
+            let raw = Geometry::new(x.unwrap_or(cx), y.unwrap_or(cy), sw.unwrap_or(cw), sh.unwrap_or(ch));
+            let g = raw_to_visual(&raw, &border, &csd_border).clamp(&work_area);
+            let (x, y, w, h) = visual_to_raw(&g, &border, &csd_border);
+            (Some(x), Some(y), Some(w), Some(h))
         } else {
-            (None, None)
+            (x, y, sw, sh)
         };
 
         // Execute if reason to
-        debug!("place: {:?}, {:?}, {}, {}", x, y, w, h);
-        wm.move_resize_window(self.id, gravity, x, y, sw, sh)
+        debug!(id = self.id, x = ?x, y = ?y, w = ?sw, h = ?sh, "place");
+        wm.move_resize_window(self.id, gravity, x, y, sw, sh)?;
+        self.placed.set(Some((x, y, sw, sh)));
+        Ok(())
+    }
+}
+
+/// Convert the given visual geometry back into the raw geometry the window manager expects
+///
+/// ### Arguments
+/// * `g` - Window's visual geometry
+/// * `border` - Window's border left, right, top, and bottom
+/// * `csd_border` - Client side border left, right, top, and bottom
+///
+/// ### Returns
+/// * `(x, y, w, h)` raw geometry of the window
+fn visual_to_raw(g: &Geometry, border: &Border, csd_border: &Border) -> (i32, i32, u32, u32) {
+    if csd_border.any() {
+        (g.x - csd_border.l as i32, g.y - csd_border.t as i32, g.w + csd_border.w(), g.h + csd_border.h())
+    } else {
+        (g.x, g.y, g.w.saturating_sub(border.w()), g.h.saturating_sub(border.h()))
+    }
+}
+
+/// Process details read from `/proc/<pid>`
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ProcStat {
+    comm: String,                    // process name
+    ppid: i32,                       // parent process id
+    session: i32,                    // session id
+    exe: Option<std::path::PathBuf>, // executable if readable
+}
+
+/// Read the name, parent pid, session id and executable of the given process from `/proc/<pid>`
+///
+/// ### Arguments
+/// * `pid` - process to read
+fn proc_stat(pid: i32) -> Option<ProcStat> {
+    let mut stat = parse_proc_stat(&std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?)?;
+    stat.exe = std::fs::read_link(format!("/proc/{}/exe", pid)).ok();
+    Some(stat)
+}
+
+/// Parse the name, parent pid and session id from the contents of `/proc/<pid>/stat`. The process
+/// name is wrapped in parens and may itself contain spaces and parens so fields are read after the
+/// last closing paren i.e. `state ppid pgrp session ...`
+///
+/// ### Arguments
+/// * `stat` - contents of the stat file
+fn parse_proc_stat(stat: &str) -> Option<ProcStat> {
+    let (start, end) = (stat.find('(')?, stat.rfind(')')?);
+    let mut fields = stat.get(end + 1..)?.split_whitespace().skip(1);
+    let ppid = fields.next()?.parse().ok()?;
+    let session = fields.nth(1)?.parse().ok()?;
+    Some(ProcStat {
+        comm: stat.get(start + 1..end)?.to_owned(),
+        ppid,
+        session,
+        exe: None,
+    })
+}
+
+/// Walk up the ancestors of the given process while they are the same program in the same session
+///
+/// ### Arguments
+/// * `pid` - process to start from
+/// * `stat` - lookup of the process details by pid
+///
+/// ### Returns
+/// * the topmost ancestor running the same program or None if the process doesn't exist
+fn app_root_pid(pid: i32, stat: impl Fn(i32) -> Option<ProcStat>) -> Option<i32> {
+    let own = stat(pid)?;
+    let same = |x: &ProcStat| {
+        x.session == own.session
+            && x.comm == own.comm
+            && (x.exe.is_none() || own.exe.is_none() || x.exe == own.exe)
+    };
+    let (mut pid, mut ppid) = (pid, own.ppid);
+    while pid != own.session && ppid > 1 && ppid != own.session {
+        match stat(ppid) {
+            Some(parent) if same(&parent) => (pid, ppid) = (ppid, parent.ppid),
+            _ => break,
+        }
+    }
+    Some(pid)
+}
+
+/// Convert the given raw geometry into the visual geometry the user sees on screen
+///
+/// ### Arguments
+/// * `g` - Window's raw geometry
+/// * `border` - Window's border left, right, top, and bottom
+/// * `csd_border` - Client side border left, right, top, and bottom
+///
+/// ### Returns
+/// * visual geometry of the window
+fn raw_to_visual(g: &Geometry, border: &Border, csd_border: &Border) -> Geometry {
+    if csd_border.any() {
+        Geometry::new(
+            g.x + csd_border.l as i32,
+            g.y + csd_border.t as i32,
+            g.w.saturating_sub(csd_border.w()),
+            g.h.saturating_sub(csd_border.h()),
+        )
+    } else {
+        Geometry::new(g.x, g.y, g.w + border.w(), g.h + border.h())
+    }
+}
+
+/// Move the given geometry from one area to another keeping its position relative to the area
+/// e.g. a window a third of the way across the source area ends up a third of the way across the
+/// destination area. The size is kept and the result clamped into the destination area.
+///
+/// ### Arguments
+/// * `g` - Window's visual geometry
+/// * `from` - area the window is currently in
+/// * `to` - area to move the window to
+pub(crate) fn relocate(g: &Geometry, from: &Geometry, to: &Geometry) -> Geometry {
+    let rx = (g.x - from.x) as f32 / from.w.max(1) as f32;
+    let ry = (g.y - from.y) as f32 / from.h.max(1) as f32;
+    let x = to.x + (rx * to.w as f32) as i32;
+    let y = to.y + (ry * to.h as f32) as i32;
+    Geometry::new(x, y, g.w, g.h).clamp(to)
+}
+
+/// Pack the given sizes into the area in rows (shelves) from the top left without gaps. Each size
+/// is placed to the right of the previous one until the row is full, then a new row is started
+/// below the tallest size of the previous row.
+///
+/// ### Arguments
+/// * `area` - work area to pack into
+/// * `sizes` - (w, h) sizes to pack in order
+///
+/// ### Returns
+/// * (x, y) position for each size in the same order
+pub(crate) fn shelf_pack(area: &Geometry, sizes: &[(u32, u32)]) -> Vec<(i32, i32)> {
+    let (mut x, mut y, mut shelf) = (area.x, area.y, 0);
+    let mut positions = vec![];
+    for &(w, h) in sizes {
+        // Start a new shelf if this size doesn't fit in the current one
+        if x > area.x && x + w as i32 > area.x + area.w as i32 {
+            x = area.x;
+            y += shelf as i32;
+            shelf = 0;
+        }
+        positions.push((x, y));
+        x += w as i32;
+        shelf = shelf.max(h);
+    }
+    positions
+}
+
+/// Find the candidate nearest to the given point in the given direction
+///
+/// ### Arguments
+/// * `from` - point to search from e.g. a window's center
+/// * `direction` - one of Left, Right, Top or Bottom
+/// * `candidates` - window ids with their centers
+///
+/// ### Returns
+/// * id of the candidate within the direction's cone with the lowest weighted distance
+fn nearest_in_direction(from: (i32, i32), direction: &Position, candidates: &[(u32, (i32, i32))]) -> Option<u32> {
+    candidates
+        .iter()
+        .filter_map(|&(id, (x, y))| {
+            let (dx, dy) = ((x - from.0) as i64, (y - from.1) as i64);
+
+            // Distance along the direction and distance off to the side of it
+            let (ahead, side) = match direction {
+                Position::Left => (-dx, dy.abs()),
+                Position::Right => (dx, dy.abs()),
+                Position::Top => (-dy, dx.abs()),
+                Position::Bottom => (dy, dx.abs()),
+                _ => return None,
+            };
+            if ahead <= 0 || side > ahead {
+                return None;
+            }
+            Some((ahead + 2 * side, id))
+        })
+        .min()
+        .map(|(_, id)| id)
+}
+
+/// Shrink the given area to exclude the reserved areas of any struts that overlap it
+///
+/// ### Arguments
+/// * `area` - Work area to shrink
+/// * `struts` - Screen edge and reserved area of each strut
+fn subtract_struts(area: &Geometry, struts: &[(Position, Geometry)]) -> Geometry {
+    let (mut l, mut t) = (area.x, area.y);
+    let (mut r, mut b) = (area.x + area.w as i32, area.y + area.h as i32);
+    for (edge, strut) in struts.iter() {
+        if strut.intersect(area).is_none() {
+            continue;
+        }
+        match edge {
+            Position::Left => l = l.max(strut.x + strut.w as i32),
+            Position::Right => r = r.min(strut.x),
+            Position::Top => t = t.max(strut.y + strut.h as i32),
+            Position::Bottom => b = b.min(strut.y),
+            _ => (),
+        }
+    }
+    Geometry::new(l, t, (r - l).max(0) as u32, (b - t).max(0) as u32)
+}
+
+/// Determine where to move the given geometry such that it is on screen
+///
+/// ### Arguments
+/// * `g` - Window's visual geometry
+/// * `areas` - Full geometry and work area of all monitors
+///
+/// ### Returns
+/// * new geometry clamped into the nearest work area or None if at least half the window is visible
+fn rescue_geometry(g: &Geometry, areas: &[(Geometry, Geometry)]) -> Option<Geometry> {
+    let visible: u64 = areas.iter().filter_map(|(x, _)| g.intersect(x)).map(|x| x.w as u64 * x.h as u64).sum();
+    if visible * 2 >= g.w as u64 * g.h as u64 {
+        return None;
     }
+
+    // Find the monitor nearest to the window's center
+    let (cx, cy) = g.center();
+    let (_, area) = areas.iter().min_by_key(|(a, _)| {
+        let dx = (cx - cx.clamp(a.x, a.x + a.w as i32)) as i64;
+        let dy = (cy - cy.clamp(a.y, a.y + a.h as i32)) as i64;
+        dx * dx + dy * dy
+    })?;
+    Some(g.clamp(area))
 }
 
 /// Translate position enum values into (x, y) cordinates but takes no direct action on the window.
@@ -403,6 +2399,10 @@ fn translate_pos(
         area.h as i32 - size.h as i32 - border.h() as i32
     };
 
+    // Windows larger than the work area keep their top left corner on screen
+    let (cx, lxr) = (cx.max(lxl), lxr.max(lxl));
+    let (cy, by) = (cy.max(ty), by.max(ty));
+
     Ok(match pos {
         Position::Center => (Some(cx), Some(cy)),
         Position::Left => (Some(lxl), None),
@@ -512,23 +2512,22 @@ fn translate_shape(
 
                 // 3/4 short side x 4x3 sized long size
                 Shape::Medium => {
-                    let (w, h) = if area.h < area.w {
-                        let h = fh as f32 * 0.75;
-                        ((h * 4.0 / 3.0) as u32, h as u32)
-                    } else {
-                        let w = fw as f32 * 0.75;
-                        (w as u32, (w * 4.0 / 3.0) as u32)
-                    };
+                    let (w, h) = aspect_size(fw, fh, area, 4, 3, 0.75);
                     (None, Some(w), Some(h))
                 },
 
                 // Full short side x 4x3 sized long size
                 Shape::Large => {
-                    let (w, h) = if area.h < area.w {
-                        ((fh as f32 * 4.0 / 3.0) as u32, fh)
-                    } else {
-                        (fw, (fw as f32 * 4.0 / 3.0) as u32)
-                    };
+                    let (w, h) = aspect_size(fw, fh, area, 4, 3, 1.0);
+                    (None, Some(w), Some(h))
+                },
+
+                // Fill fraction of the short side x ratio sized long side
+                Shape::Aspect(wr, hr, fill) => {
+                    if *wr == 0 || *hr == 0 {
+                        return Err(WmCtlError::InvalidWinShape(shape.to_string()).into());
+                    }
+                    let (w, h) = aspect_size(fw, fh, area, *wr, *hr, check_fraction(*fill, 0.0)?);
                     (None, Some(w), Some(h))
                 },
 
@@ -576,9 +2575,299 @@ fn translate_shape(
     })
 }
 
+// Validate the given fraction of an area is in the range 0.0 < frac <= 1.0 as an empty size is
+// never valid and is no smaller than the given minimum
+fn check_fraction(frac: f32, min: f32) -> WmCtlResult<f32> {
+    match frac > 0.0 && frac >= min && frac <= 1.0 {
+        true => Ok(frac),
+        false => Err(WmCtlError::InvalidFraction(frac.to_string()).into()),
+    }
+}
+
+/// Calculate the size filling the given fraction of the short side of the area with the long side
+/// sized according to the ratio.
+///
+/// ### Arguments
+/// * `fw` - full width available to the window
+/// * `fh` - full height available to the window
+/// * `area` - Window manager's work area (width, height)
+/// * `wr` - width ratio e.g. 16 of 16:9
+/// * `hr` - height ratio e.g. 9 of 16:9
+/// * `fill` - fraction of the short side to fill
+///
+/// ### Returns
+/// * `(w, h)` size scaled down as needed to fit within the full width and height e.g. when the
+///   long side would overflow on near square or portrait monitors
+fn aspect_size(fw: u32, fh: u32, area: &Rect, wr: u32, hr: u32, fill: f32) -> (u32, u32) {
+    let (mut w, mut h) = if area.h < area.w {
+        let h = fh as f32 * fill;
+        (h * wr as f32 / hr as f32, h)
+    } else {
+        let w = fw as f32 * fill;
+        (w, w * wr as f32 / hr as f32)
+    };
+
+    // Clamp to the full size keeping the ratio
+    if w > fw as f32 {
+        h = h * fw as f32 / w;
+        w = fw as f32;
+    }
+    if h > fh as f32 {
+        w = w * fh as f32 / h;
+        h = fh as f32;
+    }
+    (w as u32, h as u32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_window_is_send_sync() {
+        // Windows are shared across threads e.g. by async callers
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Window>();
+
+        // Clones track their placement independently
+        let win = Window::new(1);
+        win.placed.set(Some((Some(1), Some(2), None, None)));
+        let clone = win.clone();
+        clone.placed.set(None);
+        assert_eq!(win.placed.get(), Some((Some(1), Some(2), None, None)));
+    }
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let stat = "4242 (Web Content) S 4200 4200 3100 0 -1 4194560 12 0 0 0";
+        let expected = ProcStat {
+            comm: "Web Content".to_owned(),
+            ppid: 4200,
+            session: 3100,
+            exe: None,
+        };
+        assert_eq!(parse_proc_stat(stat), Some(expected));
+
+        // Names containing parens and spaces
+        let stat = "4242 (electron (gpu) x) S 1 4242 4242 0 -1 4194560";
+        let stat = parse_proc_stat(stat).unwrap();
+        assert_eq!((stat.comm.as_str(), stat.ppid, stat.session), ("electron (gpu) x", 1, 4242));
+        assert_eq!(parse_proc_stat("4242 (bash"), None);
+    }
+
+    #[test]
+    fn test_app_root_pid() {
+        // Desktop session launching a panel which launches the apps
+        let stats: HashMap<i32, ProcStat> = [
+            "100 (xfce4-session) S 1 100 100 0",
+            "200 (xfce4-panel) S 100 100 100 0",
+            "300 (firefox) S 200 100 100 0",
+            "400 (chrome) S 200 100 100 0",
+            "401 (chrome) S 400 100 100 0",
+            "402 (chrome) S 401 100 100 0",
+        ]
+        .iter()
+        .map(|x| (x.split(' ').next().unwrap().parse().unwrap(), parse_proc_stat(x).unwrap()))
+        .collect();
+        let stat = |pid: i32| stats.get(&pid).cloned();
+
+        // Apps launched from the panel stop at themselves rather than the session
+        assert_eq!(app_root_pid(300, stat), Some(300));
+
+        // Helper processes walk up to the app's main process
+        assert_eq!(app_root_pid(402, stat), Some(400));
+
+        // Ancestors running a different executable end the walk
+        let exe = |pid: i32| {
+            let mut x = stat(pid)?;
+            x.exe = Some(if pid == 400 { "/usr/bin/chrome-wrapper" } else { "/opt/chrome/chrome" }.into());
+            Some(x)
+        };
+        assert_eq!(app_root_pid(402, exe), Some(401));
+        assert_eq!(app_root_pid(999, stat), None);
+    }
+
+    #[test]
+    fn test_raw_visual_round_trip() {
+        let g = Geometry::new(100, 200, 800, 600);
+
+        // With WM borders
+        let b = Border::new(4, 4, 28, 4);
+        let (x, y, w, h) = visual_to_raw(&g, &b, &Border::default());
+        assert_eq!((x, y, w, h), (100, 200, 792, 568));
+        assert_eq!(raw_to_visual(&Geometry::new(x, y, w, h), &b, &Border::default()), g);
+
+        // With CSD borders
+        let c = Border::new(23, 23, 15, 31);
+        let (x, y, w, h) = visual_to_raw(&g, &b, &c);
+        assert_eq!((x, y, w, h), (77, 185, 846, 646));
+        assert_eq!(raw_to_visual(&Geometry::new(x, y, w, h), &b, &c), g);
+    }
+
+    #[test]
+    fn test_shelf_pack() {
+        let area = Geometry::new(0, 30, 1000, 800);
+        let sizes = vec![(400, 300), (500, 200), (300, 300), (1200, 100), (200, 200)];
+        assert_eq!(shelf_pack(&area, &sizes), vec![(0, 30), (400, 30), (0, 330), (0, 630), (0, 730)]);
+        assert_eq!(shelf_pack(&area, &[]), vec![]);
+    }
+
+    #[test]
+    fn test_nearest_in_direction() {
+        let candidates = vec![
+            (1, (100, 500)),
+            (2, (900, 520)),
+            (3, (700, 100)),
+            (4, (1500, 500)),
+            (5, (500, 900)),
+        ];
+
+        // Straight ahead beats further away or off to the side
+        assert_eq!(nearest_in_direction((500, 500), &Position::Right, &candidates), Some(2));
+        assert_eq!(nearest_in_direction((500, 500), &Position::Left, &candidates), Some(1));
+        assert_eq!(nearest_in_direction((500, 500), &Position::Top, &candidates), Some(3));
+        assert_eq!(nearest_in_direction((500, 500), &Position::Bottom, &candidates), Some(5));
+
+        // Nothing in that direction
+        assert_eq!(nearest_in_direction((1500, 500), &Position::Right, &candidates), None);
+        assert_eq!(nearest_in_direction((500, 500), &Position::Center, &candidates), None);
+    }
+
+    #[test]
+    fn test_rescue_geometry() {
+        let areas = vec![
+            (Geometry::new(0, 0, 1920, 1080), Geometry::new(0, 0, 1920, 1050)),
+            (Geometry::new(1920, 0, 2560, 1440), Geometry::new(1920, 0, 2560, 1415)),
+        ];
+
+        // Fully visible windows are left alone
+        assert_eq!(rescue_geometry(&Geometry::new(100, 100, 500, 500), &areas), None);
+
+        // Mostly visible windows are left alone
+        assert_eq!(rescue_geometry(&Geometry::new(-200, 100, 500, 500), &areas), None);
+
+        // Windows outside the work area but on a monitor e.g. over a panel are left alone
+        assert_eq!(rescue_geometry(&Geometry::new(0, 1050, 1920, 30), &areas), None);
+
+        // Mostly off screen windows are moved into the nearest work area
+        let g = rescue_geometry(&Geometry::new(-400, 100, 500, 500), &areas);
+        assert_eq!(g, Some(Geometry::new(0, 100, 500, 500)));
+
+        // Windows on an unplugged monitor are moved into the nearest work area
+        let g = rescue_geometry(&Geometry::new(5000, 200, 800, 600), &areas);
+        assert_eq!(g, Some(Geometry::new(3680, 200, 800, 600)));
+
+        // Windows too large for the work area are shrunk to fit
+        let g = rescue_geometry(&Geometry::new(-3000, -3000, 3000, 2000), &areas);
+        assert_eq!(g, Some(Geometry::new(0, 0, 1920, 1050)));
+    }
+
+    #[test]
+    fn test_relocate() {
+        let from = Geometry::new(0, 0, 1920, 1080);
+        let to = Geometry::new(1920, 30, 2560, 1410);
+
+        // Relative position is kept
+        let g = relocate(&Geometry::new(480, 270, 800, 600), &from, &to);
+        assert_eq!(g, Geometry::new(2560, 382, 800, 600));
+
+        // Clamped into a smaller destination
+        let to = Geometry::new(1920, 0, 1280, 1024);
+        let g = relocate(&Geometry::new(1800, 1000, 800, 600), &from, &to);
+        assert_eq!(g, Geometry::new(2400, 424, 800, 600));
+    }
+
+    #[test]
+    fn test_subtract_struts() {
+        // Work area that wrongly includes a bottom panel on the left monitor
+        let area = Geometry::new(0, 0, 1920, 1080);
+        let struts = vec![
+            (Position::Bottom, Geometry::new(0, 1040, 1920, 40)),
+            (Position::Top, Geometry::new(1920, 0, 2560, 30)),
+        ];
+        assert_eq!(subtract_struts(&area, &struts), Geometry::new(0, 0, 1920, 1040));
+
+        // Struts on another monitor are ignored
+        let area = Geometry::new(1920, 30, 2560, 1410);
+        assert_eq!(subtract_struts(&area, &struts), area);
+
+        // Left and right struts
+        let area = Geometry::new(0, 0, 1920, 1080);
+        let struts = vec![
+            (Position::Left, Geometry::new(0, 0, 48, 1080)),
+            (Position::Right, Geometry::new(1872, 0, 48, 1080)),
+        ];
+        assert_eq!(subtract_struts(&area, &struts), Geometry::new(48, 0, 1824, 1080));
+    }
+
+    #[test]
+    fn test_translate_shape_aspect() {
+        let size = Rect::default();
+        let borders = Border::default();
+        let csd = Border::default();
+        let area = Rect { w: 2560, h: 1415 };
+
+        // 16:9 filling 90% of the height
+        let shape = Shape::Aspect(16, 9, 0.9);
+        let (g, w, h) = translate_shape(&size, &borders, &csd, &area, &shape).unwrap();
+        let fh = area.h as f32 * 0.9;
+        assert_eq!(g, None);
+        assert_eq!(w, Some((fh * 16.0 / 9.0) as u32));
+        assert_eq!(h, Some(fh as u32));
+
+        // Medium and large remain 4:3
+        let (_, w1, h1) = translate_shape(&size, &borders, &csd, &area, &Shape::Large).unwrap();
+        let (_, w2, h2) = translate_shape(&size, &borders, &csd, &area, &Shape::Aspect(4, 3, 1.0)).unwrap();
+        assert_eq!((w1, h1), (w2, h2));
+        assert_eq!((w1, h1), (Some((area.h as f32 * 4.0 / 3.0) as u32), Some(area.h)));
+
+        // Invalid ratios and fractions
+        assert!(translate_shape(&size, &borders, &csd, &area, &Shape::Aspect(16, 0, 0.9)).is_err());
+        assert!(translate_shape(&size, &borders, &csd, &area, &Shape::Aspect(16, 9, 1.5)).is_err());
+    }
+
+    #[test]
+    fn test_check_fraction() {
+        assert_eq!(check_fraction(0.6, 0.0).unwrap(), 0.6);
+        assert_eq!(check_fraction(1.0, 0.0).unwrap(), 1.0);
+        assert_eq!(check_fraction(0.01, 0.0).unwrap(), 0.01);
+        assert!(check_fraction(1.5, 0.0).is_err());
+        assert!(check_fraction(0.0, 0.0).is_err());
+        assert!(check_fraction(-0.1, 0.0).is_err());
+        assert!(check_fraction(f32::NAN, 0.0).is_err());
+
+        // center_fraction's minimum
+        assert_eq!(check_fraction(0.1, CENTER_FRACTION_MIN).unwrap(), 0.1);
+        assert!(check_fraction(0.05, CENTER_FRACTION_MIN).is_err());
+    }
+
+    #[test]
+    fn test_translate_shape_clamped() {
+        let size = Rect::default();
+        let borders = Border::default();
+        let csd = Border::default();
+
+        // Near square work area where 4:3 of the full height would overflow the width
+        let area = Rect { w: 1200, h: 1100 };
+        let (_, w, h) = translate_shape(&size, &borders, &csd, &area, &Shape::Large).unwrap();
+        assert_eq!((w, h), (Some(1200), Some(900)));
+        let (_, w, h) = translate_shape(&size, &borders, &csd, &area, &Shape::Medium).unwrap();
+        assert_eq!((w, h), (Some(1100), Some(825)));
+
+        // Portrait work area where 4:3 of the full width would overflow the height
+        let area = Rect { w: 1080, h: 1200 };
+        let (_, w, h) = translate_shape(&size, &borders, &csd, &area, &Shape::Large).unwrap();
+        assert_eq!((w, h), (Some(900), Some(1200)));
+
+        // Positions keep the window on screen
+        let borders = Border::new(5, 5, 10, 10);
+        let size = Rect { w: 1300, h: 1300 };
+        let (x, y) = translate_pos(&size, &borders, &csd, &area, &Position::Center).unwrap();
+        assert_eq!((x, y), (Some(0), Some(0)));
+        let (x, y) = translate_pos(&size, &borders, &csd, &area, &Position::BottomRight).unwrap();
+        assert_eq!((x, y), (Some(0), Some(0)));
+    }
 
     #[test]
     fn test_translate_shape_halfw() {
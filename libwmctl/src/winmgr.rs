@@ -24,17 +24,28 @@
 // * GetAtomName - get the name of an atom
 //
 use crate::{atoms::*, model::*, WmCtlError, WmCtlResult};
-use std::{collections::HashMap, str};
+use std::{
+    collections::{HashMap, HashSet},
+    str, thread,
+    time::{Duration, Instant},
+};
 use tracing::debug;
 
 use x11rb::{
-    connection::Connection,
-    protocol::xproto::{ConnectionExt as _, *},
+    connection::{Connection, RequestConnection},
+    properties::WmSizeHints,
+    protocol::randr::{self, ConnectionExt as _},
+    protocol::xproto::{self, ConnectionExt as _, *},
+    protocol::Event,
     rust_connection::RustConnection,
+    wrapper::ConnectionExt as _,
 };
 
 /// Window Manager provides a higher level interface to the underlying EWHM compatible window manager
-pub(crate) struct WinMgr {
+/// * Most use cases should use the global connection via the crate level functions
+/// * Use `WinMgr::new_connection` with the crate level `*_on` functions and `Window::with_winmgr` to
+///   work with multiple X displays
+pub struct WinMgr {
     conn: RustConnection,            // x11 connection
     atoms: AtomCollection,           // atom cache
     supported: HashMap<u32, String>, // cache of {id => name} for supported functions
@@ -46,10 +57,16 @@ pub(crate) struct WinMgr {
     height: u32,                     // screen height
     desktops: u32,                   // number of desktops
     compositing: bool,               // compositing manager running
+    compositor: Option<String>,      // compositing manager name
+    display: Option<String>,         // display connected to or None for the environment's
 
     // Crate properties
-    pub(crate) work_width: u32,  // work area width (i.e. minus panels)
-    pub(crate) work_height: u32, // work areas height (i.e. minus panels)
+    pub(crate) source_indication: SourceIndication, // source indication sent with client messages
+    pub(crate) ignored_classes: HashSet<String>,    // lowercase classes skipped by bulk operations
+    pub(crate) work_x: i32,                         // work area x offset (i.e. minus panels)
+    pub(crate) work_y: i32,                         // work area y offset (i.e. minus panels)
+    pub(crate) work_width: u32,                     // work area width (i.e. minus panels)
+    pub(crate) work_height: u32,                    // work areas height (i.e. minus panels)
 }
 
 impl WinMgr {
@@ -62,7 +79,24 @@ impl WinMgr {
     /// ```
     pub(crate) fn connect() -> WmCtlResult<Self> {
         debug!("connect: initializing connection...");
-        let (conn, screen) = x11rb::connect(None)?;
+        WinMgr::new_connection(None)
+    }
+
+    /// Create a window manager control instance connected to the given X11 display rather than
+    /// the global connection the crate level functions use.
+    ///
+    /// ### Arguments
+    /// * `display` - X11 display to connect to e.g. `:1` or None for the `DISPLAY` environment variable
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use std::sync::{Arc, RwLock};
+    /// use libwmctl::prelude::*;
+    /// let wm = Arc::new(RwLock::new(WinMgr::new_connection(Some(":1")).unwrap()));
+    /// let win = Window::with_winmgr(&wm, 12345);
+    /// ```
+    pub fn new_connection(display: Option<&str>) -> WmCtlResult<Self> {
+        let (conn, screen) = x11rb::connect(display)?;
 
         // Get the screen size
         let (width, height, root) = {
@@ -84,21 +118,30 @@ impl WinMgr {
             root,
             width,
             height,
+            work_x: Default::default(),
+            work_y: Default::default(),
             work_width: Default::default(),
             work_height: Default::default(),
             desktops: Default::default(),
             compositing: Default::default(),
+            compositor: Default::default(),
+            display: display.map(|x| x.to_owned()),
+            source_indication: SOURCE_INDICATION_PAGER,
+            ignored_classes: Default::default(),
         };
 
         // Fill in missing properties that require a connection and supported atoms init_caching
         let (id, name) = wm.id()?;
         wm.id = id;
         wm.name = name;
-        let (width, height) = wm.workarea()?;
+        let (x, y, width, height) = wm.workarea()?;
+        wm.work_x = x;
+        wm.work_y = y;
         wm.work_width = width;
         wm.work_height = height;
         wm.desktops = wm.desktops()?;
         wm.compositing = wm.compositing()?;
+        wm.compositor = wm.compositor_name()?;
         wm.supported = wm.supported()?;
 
         debug!("connect: screen: {}, root: {}, w: {}, h: {}", screen, root, width, height);
@@ -166,8 +209,12 @@ impl WinMgr {
             work_area: (self.work_width, self.work_height),
             screen_size: (self.width, self.height),
             desktops: self.desktops,
+            desktop_geometry: self.desktop_geometry().unwrap_or((self.width, self.height)),
+            viewport: self.viewport().unwrap_or_default(),
             compositing: self.compositing,
+            compositor: self.compositor.clone(),
             supported: self.supported.clone(),
+            supported_names: self.supported.values().cloned().collect(),
         })
     }
 
@@ -196,6 +243,46 @@ impl WinMgr {
         Ok(win)
     }
 
+    // Record the given window as the most recently used in the history. EWMH doesn't expose the
+    // focus history so it is recorded explicitly i.e. when cycling windows and on active window
+    // events while watching and stored on the root window as _WMCTL_HISTORY, WINDOW[]/32 to be
+    // shared by every wmctl process. Only windows wmctl has seen active are in the history.
+    pub(crate) fn record_active(&self, id: u32) {
+        const HISTORY_SIZE: usize = 32;
+        if id == 0 || id == self.root {
+            return;
+        }
+        let result = self.history().and_then(|mut history| {
+            if history.first() == Some(&id) {
+                return Ok(());
+            }
+            history.retain(|&x| x != id);
+            history.insert(0, id);
+            history.truncate(HISTORY_SIZE);
+            self.conn.change_property32(
+                PropMode::REPLACE,
+                self.root,
+                self.atoms._WMCTL_HISTORY,
+                AtomEnum::WINDOW,
+                &history,
+            )?;
+            self.conn.flush()?;
+            Ok(())
+        });
+        if let Err(err) = result {
+            debug!("record_active: failed to record {}: {}", id, err);
+        }
+    }
+
+    // Get the recorded active window history most recently used first
+    fn history(&self) -> WmCtlResult<Vec<u32>> {
+        let reply = self
+            .conn
+            .get_property(false, self.root, self.atoms._WMCTL_HISTORY, AtomEnum::WINDOW, 0, u32::MAX)?
+            .reply()?;
+        Ok(reply.value32().map(|x| x.collect()).unwrap_or_default())
+    }
+
     /// Get the Window Manager's supported functions.
     ///
     /// ### Examples
@@ -261,10 +348,10 @@ impl WinMgr {
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// let (w, h) = wm.workarea().unwrap();
+    /// let (x, y, w, h) = wm.workarea().unwrap();
     /// ```
-    fn workarea(&self) -> WmCtlResult<(u32, u32)> {
-        let (mut w, mut h) = (self.width, self.height);
+    fn workarea(&self) -> WmCtlResult<(i32, i32, u32, u32)> {
+        let (mut x, mut y, mut w, mut h) = (0, 0, self.width, self.height);
 
         // Defined as: _NET_WORKAREA, x, y, width, height CARDINAL[][4]/32
         // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WORKAREA`
@@ -276,18 +363,78 @@ impl WinMgr {
             .reply();
         if let Ok(reply) = reply {
             if let Some(mut values) = reply.value32() {
-                // x and y are always zero so dropping them
-                _ = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_WORKAREA x".to_owned()))?;
-                _ = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_WORKAREA y".to_owned()))?;
+                // x and y are non zero when panels are docked on the left or top of the screen
+                x = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_WORKAREA x".to_owned()))? as i32;
+                y = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_WORKAREA y".to_owned()))? as i32;
                 w = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_WORKAREA width".to_owned()))?;
                 h = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_WORKAREA height".to_owned()))?;
             }
         }
-        debug!("work_area: w: {}, h: {}", w, h);
+        debug!("work_area: x: {}, y: {}, w: {}, h: {}", x, y, w, h);
+
+        Ok((x, y, w, h))
+    }
 
+    /// Get the size of the large virtual desktop shared by all desktops. This is the screen size
+    /// unless the window manager supports large desktops with viewports e.g. fvwm.
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let (w, h) = wm.desktop_geometry().unwrap();
+    /// ```
+    pub(crate) fn desktop_geometry(&self) -> WmCtlResult<(u32, u32)> {
+        // Defined as: _NET_DESKTOP_GEOMETRY width, height, CARDINAL[2]/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_DESKTOP_GEOMETRY`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
+        // retrieve the two values.
+        let reply = self
+            .conn
+            .get_property(false, self.root, self.atoms._NET_DESKTOP_GEOMETRY, AtomEnum::CARDINAL, 0, 2)?
+            .reply()?;
+        let mut values =
+            reply.value32().ok_or(WmCtlError::PropertyNotFound("_NET_DESKTOP_GEOMETRY".to_owned()))?;
+        let w = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_DESKTOP_GEOMETRY width".to_owned()))?;
+        let h = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_DESKTOP_GEOMETRY height".to_owned()))?;
+        debug!("desktop_geometry: w: {}, h: {}", w, h);
         Ok((w, h))
     }
 
+    /// Get the top left corner of the current desktop's viewport into the large virtual desktop.
+    /// This is (0, 0) unless the window manager supports large desktops with viewports e.g. fvwm.
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let (x, y) = wm.viewport().unwrap();
+    /// ```
+    pub(crate) fn viewport(&self) -> WmCtlResult<(i32, i32)> {
+        // Defined as: _NET_DESKTOP_VIEWPORT x, y, CARDINAL[][2]/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_DESKTOP_VIEWPORT`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
+        // retrieve the values of which there will be 2 for each desktop as defined (x, y).
+        let reply = self
+            .conn
+            .get_property(false, self.root, self.atoms._NET_DESKTOP_VIEWPORT, AtomEnum::CARDINAL, 0, u32::MAX)?
+            .reply()?;
+        let values = reply
+            .value32()
+            .ok_or(WmCtlError::PropertyNotFound("_NET_DESKTOP_VIEWPORT".to_owned()))?
+            .collect::<Vec<_>>();
+
+        // Use the current desktop's viewport falling back on the first
+        let i = self.current_desktop().map(|x| (x - 1) as usize * 2).unwrap_or(0);
+        let i = if i + 1 < values.len() { i } else { 0 };
+        let (x, y) = match (values.get(i), values.get(i + 1)) {
+            (Some(x), Some(y)) => (*x as i32, *y as i32),
+            _ => return Err(WmCtlError::PropertyNotFound("_NET_DESKTOP_VIEWPORT".to_owned()).into()),
+        };
+        debug!("viewport: x: {}, y: {}", x, y);
+        Ok((x, y))
+    }
+
     /// Check if a composit manager is running
     ///
     /// ### Examples
@@ -297,15 +444,40 @@ impl WinMgr {
     /// wm.compositing().unwrap();
     /// ```
     fn compositing(&self) -> WmCtlResult<bool> {
+        let result = self.compositor_owner()? != x11rb::NONE;
+        debug!("composite_manager: {}", result);
+        Ok(result)
+    }
+
+    /// Get the name of the compositing manager if one is running
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.compositor_name().unwrap();
+    /// ```
+    pub(crate) fn compositor_name(&self) -> WmCtlResult<Option<String>> {
+        let owner = self.compositor_owner()?;
+        if owner == x11rb::NONE {
+            return Ok(None);
+        }
+
+        // Compositing managers like picom and compton name their selection owner window
+        let name = self.window_name(owner).ok();
+        debug!("compositor_name: owner: {}, name: {:?}", owner, name);
+        Ok(name)
+    }
+
+    /// Get the window id that owns the compositing manager selection or x11rb::NONE
+    fn compositor_owner(&self) -> WmCtlResult<u32> {
         // Defined as: _NET_WM_CM_Sn
         // For each screen the compositing manager manages they MUST acquire ownership of a
         // selection named _NET_WM_CM_Sn, where the suffix `n` is the screen number.
         let atom = format!("_NET_WM_CM_S{}", self.screen);
         let atom = self.conn.intern_atom(false, atom.as_bytes())?.reply()?.atom;
         let reply = self.conn.get_selection_owner(atom)?.reply()?;
-        let result = reply.owner != x11rb::NONE;
-        debug!("composite_manager: {}", result);
-        Ok(result)
+        Ok(reply.owner)
     }
 
     /// Get number of desktops
@@ -316,7 +488,7 @@ impl WinMgr {
     /// let wm = WinMgr::connect().unwrap();
     /// wm.desktops().unwrap();
     /// ```
-    fn desktops(&self) -> WmCtlResult<u32> {
+    pub(crate) fn desktops(&self) -> WmCtlResult<u32> {
         // Defined as: _NET_NUMBER_OF_DESKTOPS, CARDINAL/32
         // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_NUMBER_OF_DESKTOPS`
         // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
@@ -332,223 +504,226 @@ impl WinMgr {
         debug!("desktops: {}", num);
         Ok(num)
     }
-    /// Get windows optionally all
-    /// * when all is true for some reason the window state is not correctly returned
-    /// * when all is true the parent window is the root window for all windows
-    ///
-    /// ### Arguments
-    /// * `all` - default is to get all windows controlled by the window manager, when all is true get the super set of x11 windows
+
+    /// Get the names of the desktops in desktop order
+    /// * Returns an empty list if the window manager doesn't name its desktops
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.windows(false).unwrap()
+    /// wm.desktop_names().unwrap();
     /// ```
-    pub(crate) fn windows(&self, all: bool) -> WmCtlResult<Vec<u32>> {
-        Ok(if all {
-            // All windows in the X11 system
-            self.conn.query_tree(self.root)?.reply()?.children
-        } else {
-            // Window manager client windows which is a subset of all windows that have been
-            // reparented i.e. new ids and don't map to the same ids as their all windows selves.
-            let reply = self
-                .conn
-                .get_property(false, self.root, self.atoms._NET_CLIENT_LIST, AtomEnum::WINDOW, 0, u32::MAX)?
-                .reply()?;
-            let children = reply.value32().ok_or(WmCtlError::PropertyNotFound("_NET_CLIENT_LIST".to_owned()))?;
-            children.collect::<Vec<_>>()
-        })
+    pub(crate) fn desktop_names(&self) -> WmCtlResult<Vec<String>> {
+        // Defined as: _NET_DESKTOP_NAMES, UTF8_STRING[]
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_DESKTOP_NAMES`
+        // request message with a `self.atoms.UTF8_STRING` type response and the names will be a list of null
+        // terminated strings.
+        let reply = self
+            .conn
+            .get_property(false, self.root, self.atoms._NET_DESKTOP_NAMES, self.atoms.UTF8_STRING, 0, u32::MAX)?
+            .reply()?;
+        let mut names = vec![];
+        for name in reply.value.split(|x| *x == 0) {
+            names.push(str::from_utf8(name)?.to_owned());
+        }
+
+        // Drop the empty value following the final null terminator
+        if names.last().is_some_and(|x| x.is_empty()) {
+            names.pop();
+        }
+        debug!("desktop_names: {:?}", names);
+        Ok(names)
     }
 
-    /// Get window pid
+    /// Set the number of desktops
     ///
     /// ### Arguments
-    /// * `id` - id of the window to manipulate
+    /// * `count` - number of desktops to have, must be at least 1
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.window_pid(1234)
+    /// wm.set_desktops(4).unwrap();
     /// ```
-    pub(crate) fn window_pid(&self, id: u32) -> WmCtlResult<i32> {
-        // Defined as: _NET_WM_PID, CARDINAL/32
-        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_PID`
-        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
-        // retrieve the values of which there will be a single value.
-        let reply =
-            self.conn.get_property(false, id, self.atoms._NET_WM_PID, AtomEnum::CARDINAL, 0, u32::MAX)?.reply()?;
-        let pid = reply
-            .value32()
-            .and_then(|mut x| x.next())
-            .ok_or(WmCtlError::PropertyNotFound("_NET_WM_PID".to_owned()))?;
-        debug!("win_pid: id: {}, pid: {:?}", id, pid);
-        Ok(pid as i32)
+    pub(crate) fn set_desktops(&self, count: u32) -> WmCtlResult<()> {
+        if count < 1 {
+            return Err(WmCtlError::InvalidDesktopCount(count).into());
+        }
+        if !self.is_supported(self.atoms._NET_NUMBER_OF_DESKTOPS) {
+            return Err(WmCtlError::Unsupported("_NET_NUMBER_OF_DESKTOPS".to_owned()).into());
+        }
+        self.send_event(ClientMessageEvent::new(
+            32,
+            self.root,
+            self.atoms._NET_NUMBER_OF_DESKTOPS,
+            [count, 0, 0, 0, 0],
+        ))?;
+        debug!("set_desktops: {}", count);
+        Ok(())
     }
 
-    /// Get window name
-    ///
-    /// ### Arguments
-    /// * `id` - id of the window to manipulate
+    /// Get the current desktop
+    /// * Returns non zero based desktop number
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.window_name(1234)
+    /// wm.current_desktop().unwrap();
     /// ```
-    pub(crate) fn window_name(&self, id: u32) -> WmCtlResult<String> {
-        // Defined as: _NET_WM_NAME, UTF8_STRING
-        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_NAME`
-        // request message with a `AtomEnum::UTF8_STRING` type response and we can use the `reply.value` accessor to
+    pub(crate) fn current_desktop(&self) -> WmCtlResult<u32> {
+        // Defined as: _NET_CURRENT_DESKTOP desktop, CARDINAL/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_CURRENT_DESKTOP`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
         // retrieve the value.
-
-        // First try the _NET_WM_VISIBLE_NAME
-        let reply = self
-            .conn
-            .get_property(false, id, self.atoms._NET_WM_VISIBLE_NAME, self.atoms.UTF8_STRING, 0, u32::MAX)?
-            .reply()?;
-        if reply.type_ != x11rb::NONE {
-            if let Ok(value) = str::from_utf8(&reply.value) {
-                if value != "" {
-                    debug!("win_name: using _NET_WM_VISIBLE_NAME for: {}", value);
-                    return Ok(value.to_owned());
-                }
-            }
-        }
-
-        // Next try the _NET_WM_NAME
         let reply = self
             .conn
-            .get_property(false, id, self.atoms._NET_WM_NAME, self.atoms.UTF8_STRING, 0, u32::MAX)?
+            .get_property(false, self.root, self.atoms._NET_CURRENT_DESKTOP, AtomEnum::CARDINAL, 0, u32::MAX)?
             .reply()?;
-        if reply.type_ != x11rb::NONE {
-            if let Ok(value) = str::from_utf8(&reply.value) {
-                if value != "" {
-                    debug!("win_name: using _NET_WM_NAME for: {}", value);
-                    return Ok(value.to_owned());
-                }
-            }
-        }
-
-        // Fall back on the WM_NAME
-        let reply =
-            self.conn.get_property(false, id, AtomEnum::WM_NAME, AtomEnum::STRING, 0, u32::MAX)?.reply()?;
-        if reply.type_ != x11rb::NONE {
-            if let Ok(value) = str::from_utf8(&reply.value) {
-                if value != "" {
-                    debug!("win_name: using WM_NAME for: {}", value);
-                    return Ok(value.to_owned());
-                }
-            }
-        }
+        let desktop = reply
+            .value32()
+            .and_then(|mut x| x.next())
+            .ok_or(WmCtlError::PropertyNotFound("_NET_CURRENT_DESKTOP".to_owned()))?;
 
-        // No valid name was found
-        Err(WmCtlError::PropertyNotFound("_NET_WM_NAME | _WM_NAME".to_owned()).into())
+        // Offset to align with how desktops are typically numbered
+        debug!("current_desktop: {}", desktop + 1);
+        Ok(desktop + 1)
     }
 
-    /// Get window class which is typically the the application's name
+    /// Determine if the window manager is in "showing the desktop" mode i.e. all windows are hidden
+    /// to show the desktop.
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.window_class(1234)
+    /// wm.showing_desktop().unwrap();
     /// ```
-    pub(crate) fn window_class(&self, id: u32) -> WmCtlResult<String> {
-        let reply =
-            self.conn.get_property(false, id, AtomEnum::WM_CLASS, AtomEnum::STRING, 0, u32::MAX)?.reply()?;
-
-        // Skip the first null terminated string and extract the second
-        let iter = reply.value.into_iter().skip_while(|x| *x != 0).skip(1).take_while(|x| *x != 0);
-
-        // Extract the second null terminated string
-        let class = str::from_utf8(&iter.collect::<Vec<_>>())?.to_owned();
-        debug!("win_class: id: {}, class: {}", id, class);
-        Ok(class)
+    pub(crate) fn showing_desktop(&self) -> WmCtlResult<bool> {
+        // Defined as: _NET_SHOWING_DESKTOP desktop, CARDINAL/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_SHOWING_DESKTOP`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
+        // retrieve the value.
+        let reply = self
+            .conn
+            .get_property(false, self.root, self.atoms._NET_SHOWING_DESKTOP, AtomEnum::CARDINAL, 0, u32::MAX)?
+            .reply()?;
+        let showing = reply
+            .value32()
+            .and_then(|mut x| x.next())
+            .ok_or(WmCtlError::PropertyNotFound("_NET_SHOWING_DESKTOP".to_owned()))?;
+        debug!("showing_desktop: {}", showing);
+        Ok(showing == 1)
     }
 
-    /// Get window kind
+    /// Ask the window manager to switch the current desktop
     ///
     /// ### Arguments
-    /// * `id` - id of the window to manipulate
+    /// * `desktop` - zero based desktop number as defined by _NET_CURRENT_DESKTOP
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.window_kind(1234)
+    /// wm.switch_desktop(1).unwrap();
     /// ```
-    pub(crate) fn window_kind(&self, id: u32) -> WmCtlResult<Kind> {
-        // Defined as: _NET_WM_WINDOW_TYPE, ATOM[]/32
-        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_WINDOW_TYPE`
-        // request message with a `AtomEnum::ATOM` type response and we can use the `reply.value32()` accessor to
-        // retrieve the value.
-        let reply = self
-            .conn
-            .get_property(false, id, self.atoms._NET_WM_WINDOW_TYPE, AtomEnum::ATOM, 0, u32::MAX)?
-            .reply()?;
-        let typ = reply
-            .value32()
-            .and_then(|mut x| x.next())
-            .ok_or(WmCtlError::PropertyNotFound("_NET_WM_WINDOW_TYPE".to_owned()))?;
-        let _kind = Kind::from(&self.atoms, typ)?;
-        debug!("win_kind: id: {}, kind: {:?}", id, _kind);
-        Ok(_kind)
+    pub(crate) fn switch_desktop(&self, desktop: u32) -> WmCtlResult<()> {
+        self.send_event(ClientMessageEvent::new(
+            32,
+            self.root,
+            self.atoms._NET_CURRENT_DESKTOP,
+            [desktop, x11rb::CURRENT_TIME, 0, 0, 0],
+        ))?;
+        debug!("switch_desktop: {}", desktop);
+        Ok(())
     }
 
-    /// Get window state
+    /// Enter or leave the window manager's "showing the desktop" mode
     ///
     /// ### Arguments
-    /// * `id` - id of the window to manipulate
+    /// * `enable` - true to hide all windows and show the desktop, false to restore them
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.window_state(1234)
+    /// wm.set_showing_desktop(true).unwrap();
     /// ```
-    pub(crate) fn window_state(&self, id: u32) -> WmCtlResult<Vec<State>> {
-        // Defined as: _NET_WM_STATE, ATOM[]
-        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_STATE`
-        // request message with a `AtomEnum::ATOM` type response and we can use the `reply.value32()` accessor to
-        // retrieve the values of which there will be a single value.
-        let reply =
-            self.conn.get_property(false, id, self.atoms._NET_WM_STATE, AtomEnum::ATOM, 0, u32::MAX)?.reply()?;
+    pub(crate) fn set_showing_desktop(&self, enable: bool) -> WmCtlResult<()> {
+        if !self.is_supported(self.atoms._NET_SHOWING_DESKTOP) {
+            return Err(WmCtlError::Unsupported("_NET_SHOWING_DESKTOP".to_owned()).into());
+        }
+        self.send_event(ClientMessageEvent::new(
+            32,
+            self.root,
+            self.atoms._NET_SHOWING_DESKTOP,
+            [enable as u32, 0, 0, 0, 0],
+        ))?;
+        debug!("set_showing_desktop: {}", enable);
+        Ok(())
+    }
 
-        let mut states = vec![];
-        if reply.value_len > 0 {
-            for state in reply.value32().ok_or(WmCtlError::PropertyNotFound("_NET_WM_STATE".to_owned()))? {
-                let state = State::from(&self.atoms, state)?;
-                states.push(state);
-            }
-            debug!("win_state: id: {}, state: {:?}", id, states);
+    /// Get the monitors as reported by the RandR extension. The work area for each monitor is the
+    /// portion of the monitor that overlaps with the window manager's work area.
+    /// * Returns an empty list if the RandR extension isn't available
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.monitors().unwrap();
+    /// ```
+    pub(crate) fn monitors(&self) -> WmCtlResult<Vec<Monitor>> {
+        if self.conn.extension_information(randr::X11_EXTENSION_NAME)?.is_none() {
+            debug!("monitors: randr extension not available");
+            return Ok(vec![]);
         }
-        Ok(states)
+        let reply = self.conn.randr_get_monitors(self.root, true)?.reply()?;
+        let names = self.atom_map(&reply.monitors.iter().map(|x| x.name).collect::<Vec<_>>())?;
+        let work_area = Geometry::new(self.work_x, self.work_y, self.work_width, self.work_height);
+
+        let mut monitors = vec![];
+        for info in reply.monitors.iter() {
+            let geometry = Geometry::new(info.x as i32, info.y as i32, info.width as u32, info.height as u32);
+            let monitor = Monitor {
+                name: names.get(&info.name).cloned().unwrap_or_default(),
+                primary: info.primary,
+                geometry,
+                work_area: geometry.intersect(&work_area).unwrap_or(geometry),
+            };
+            debug!("monitors: {:?}", monitor);
+            monitors.push(monitor);
+        }
+        Ok(monitors)
     }
 
-    /// Get window parent
+    /// Get the monitor by name
     ///
     /// ### Arguments
-    /// * `id` - id of the window to manipulate
+    /// * `name` - name of the monitor e.g. HDMI-1
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.window_parent(1234)
+    /// wm.monitor("HDMI-1").unwrap();
     /// ```
-    #[allow(dead_code)]
-    pub(crate) fn window_parent(&self, id: u32) -> WmCtlResult<crate::Window> {
-        let tree = self.conn.query_tree(id)?.reply()?;
-        let parent_id = tree.parent;
-        debug!("win_parent: id: {}, parent: {:?}", id, parent_id);
-        Ok(crate::Window::new(parent_id))
+    pub(crate) fn monitor(&self, name: &str) -> WmCtlResult<Monitor> {
+        let mut monitors = self.monitors()?;
+        match monitors.iter().position(|x| x.name == name) {
+            Some(i) => Ok(monitors.swap_remove(i)),
+            None => {
+                let names = monitors.iter().map(|x| x.name.as_str()).collect::<Vec<_>>();
+                Err(WmCtlError::MonitorNotFound(name.to_owned(), names.join(", ")).into())
+            },
+        }
     }
 
-    /// Get window desktop
-    /// * Returns non zero based desktop number
+    /// Get the monitor the window is on as determined by the window's center. Falls back on the
+    /// primary monitor then the first monitor if the window's center isn't on any monitor.
+    /// * Returns None if the RandR extension isn't available
     ///
     /// ### Arguments
     /// * `id` - id of the window to manipulate
@@ -557,37 +732,730 @@ impl WinMgr {
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.window_desktop(1234)
+    /// wm.window_monitor(1234).unwrap();
     /// ```
-    pub(crate) fn window_desktop(&self, id: u32) -> WmCtlResult<i32> {
-        // Defined as: _NET_WM_DESKTOP desktop, CARDINAL/32
-        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_DESKTOP`
-        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
-        // retrieve the values of which there will be a single value.
-        let reply = self
-            .conn
-            .get_property(false, id, self.atoms._NET_WM_DESKTOP, AtomEnum::CARDINAL, 0, u32::MAX)?
-            .reply()?;
-        let mut desktop = reply.value32().and_then(|mut x| x.next()).map_or(-1, |x| x as i32);
-
-        // Offset to align with how desktops are typically numbered
-        if desktop != -1 {
-            desktop += 1;
-        }
-
-        debug!("win_desktop: id: {}, desktop: {}", id, desktop);
-        Ok(desktop as i32)
+    pub(crate) fn window_monitor(&self, id: u32) -> WmCtlResult<Option<Monitor>> {
+        let (cx, cy) = Geometry::from(self.window_visual_geometry(id)?).center();
+        let mut monitors = self.monitors()?;
+        let i = monitors
+            .iter()
+            .position(|x| x.geometry.contains(cx, cy))
+            .or_else(|| monitors.iter().position(|x| x.primary))
+            .or(if monitors.is_empty() { None } else { Some(0) });
+        Ok(i.map(|i| monitors.swap_remove(i)))
     }
 
-    /// Get window visual geometry.
-    /// Geometry is a calculated value that represents the window's size and position including it's
-    /// frame or visually perceived frame. Be careful in calculating from this value as frame/application
-    /// borders are added and subtracted and positioning changed in different uses cases called out
-    /// below to make these values more intuitive visually. Other apps like xdotool or xwininfo use
-    /// the --frame option to include the window manager's frame in the calculation which is somewhat
-    /// akin to what is happending here only this also takes into account Client Side Decorations (CSD).
+    /// Get the full geometry of the screen spanning all monitors
     ///
-    /// * For Window Manager decorated windows this means this function is computing the window size
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let screen = wm.screen_geometry();
+    /// ```
+    pub(crate) fn screen_geometry(&self) -> Geometry {
+        Geometry::new(0, 0, self.width, self.height)
+    }
+
+    /// Get the work area of the monitor containing the given point. Falls back on the window
+    /// manager's work area if the RandR extension isn't available or no monitor contains the point.
+    ///
+    /// ### Arguments
+    /// * `x` - x coordinate of the point in screen coordinates
+    /// * `y` - y coordinate of the point in screen coordinates
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let area = wm.work_area_at(100, 100).unwrap();
+    /// ```
+    pub(crate) fn work_area_at(&self, x: i32, y: i32) -> WmCtlResult<Geometry> {
+        Ok(match self.monitors()?.into_iter().find(|m| m.geometry.contains(x, y)) {
+            Some(monitor) => monitor.work_area,
+            None => Geometry::new(self.work_x, self.work_y, self.work_width, self.work_height),
+        })
+    }
+
+    /// Get the current position of the pointer in screen coordinates
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let (x, y) = wm.pointer().unwrap();
+    /// ```
+    pub(crate) fn pointer(&self) -> WmCtlResult<(i32, i32)> {
+        let reply = self.conn.query_pointer(self.root)?.reply()?;
+        let (x, y) = (reply.root_x as i32, reply.root_y as i32);
+        debug!("pointer: x: {}, y: {}", x, y);
+        Ok((x, y))
+    }
+
+    /// Get the managed window containing the given point in screen coordinates. Walks down the
+    /// window tree from the root through the top most child containing the point at each level
+    /// e.g. through the window manager's frame until a managed client window is found.
+    /// * Returns None if only the root or desktop window is at the point
+    /// * Returns an error if the point is outside the 16 bit X11 coordinate range
+    ///
+    /// ### Arguments
+    /// * `x` - x coordinate of the point in screen coordinates
+    /// * `y` - y coordinate of the point in screen coordinates
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let id = wm.window_at(100, 100).unwrap();
+    /// ```
+    pub(crate) fn window_at(&self, x: i32, y: i32) -> WmCtlResult<Option<u32>> {
+        // X11 coordinates are 16 bit so points outside that range can't be on the screen
+        let (x, y) = match (i16::try_from(x), i16::try_from(y)) {
+            (Ok(x), Ok(y)) => (x, y),
+            _ => return Err(WmCtlError::InvalidWinPosition(format!("{}, {}", x, y)).into()),
+        };
+        let clients = self.windows(false)?;
+        let mut id = self.conn.translate_coordinates(self.root, self.root, x, y)?.reply()?.child;
+        while id != x11rb::NONE && !clients.contains(&id) {
+            id = self.conn.translate_coordinates(self.root, id, x, y)?.reply()?.child;
+        }
+        let id = Some(id).filter(|&id| id != x11rb::NONE && self.window_kind(id).ok() != Some(Kind::Desktop));
+        debug!("win_at: x: {}, y: {}, id: {:?}", x, y, id);
+        Ok(id)
+    }
+
+    /// Get the work area of the monitor the window is on. Falls back on the window manager's work
+    /// area if the RandR extension isn't available.
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let area = wm.window_work_area(1234).unwrap();
+    /// ```
+    pub(crate) fn window_work_area(&self, id: u32) -> WmCtlResult<Geometry> {
+        Ok(match self.window_monitor(id)? {
+            Some(monitor) => monitor.work_area,
+            None => Geometry::new(self.work_x, self.work_y, self.work_width, self.work_height),
+        })
+    }
+
+    /// Get the areas of the screen reserved by dock windows e.g. panels via their struts
+    /// * Uses _NET_WM_STRUT_PARTIAL falling back on _NET_WM_STRUT which reserves the full edge
+    ///
+    /// ### Returns
+    /// * `(edge, area)` screen edge the strut is attached to and the reserved area
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let struts = wm.struts().unwrap();
+    /// ```
+    pub(crate) fn struts(&self) -> WmCtlResult<Vec<(Position, Geometry)>> {
+        let (sw, sh) = (self.width, self.height);
+        let mut struts = vec![];
+        for id in self.windows(false)? {
+            if self.window_kind(id).ok() != Some(Kind::Dock) {
+                continue;
+            }
+
+            // Defined as: _NET_WM_STRUT_PARTIAL, left, right, top, bottom, left_start_y, left_end_y,
+            // right_start_y, right_end_y, top_start_x, top_end_x, bottom_start_x, bottom_end_x, CARDINAL[12]/32
+            let mut vals = self
+                .conn
+                .get_property(false, id, self.atoms._NET_WM_STRUT_PARTIAL, AtomEnum::CARDINAL, 0, 12)?
+                .reply()?
+                .value32()
+                .map(|x| x.collect::<Vec<_>>())
+                .unwrap_or_default();
+            if vals.len() < 12 {
+                // Defined as: _NET_WM_STRUT, left, right, top, bottom, CARDINAL[4]/32
+                vals = self
+                    .conn
+                    .get_property(false, id, self.atoms._NET_WM_STRUT, AtomEnum::CARDINAL, 0, 4)?
+                    .reply()?
+                    .value32()
+                    .map(|x| x.collect::<Vec<_>>())
+                    .unwrap_or_default();
+                if vals.len() < 4 {
+                    continue;
+                }
+                vals.extend([0, sh - 1, 0, sh - 1, 0, sw - 1, 0, sw - 1]);
+            }
+
+            let (l, r, t, b) = (vals[0], vals[1], vals[2], vals[3]);
+            let span = |start: u32, end: u32| end.saturating_sub(start) + 1;
+            if l > 0 {
+                struts.push((Position::Left, Geometry::new(0, vals[4] as i32, l, span(vals[4], vals[5]))));
+            }
+            if r > 0 {
+                struts.push((
+                    Position::Right,
+                    Geometry::new(sw.saturating_sub(r) as i32, vals[6] as i32, r, span(vals[6], vals[7])),
+                ));
+            }
+            if t > 0 {
+                struts.push((Position::Top, Geometry::new(vals[8] as i32, 0, span(vals[8], vals[9]), t)));
+            }
+            if b > 0 {
+                struts.push((
+                    Position::Bottom,
+                    Geometry::new(vals[10] as i32, sh.saturating_sub(b) as i32, span(vals[10], vals[11]), b),
+                ));
+            }
+        }
+        debug!("struts: {:?}", struts);
+        Ok(struts)
+    }
+
+    /// Get windows optionally all
+    /// * when all is true for some reason the window state is not correctly returned
+    /// * when all is true the parent window is the root window for all windows
+    ///
+    /// ### Arguments
+    /// * `all` - default is to get all windows controlled by the window manager, when all is true get the super set of x11 windows
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.windows(false).unwrap()
+    /// ```
+    pub(crate) fn windows(&self, all: bool) -> WmCtlResult<Vec<u32>> {
+        Ok(if all {
+            // All windows in the X11 system
+            self.conn.query_tree(self.root)?.reply()?.children
+        } else {
+            // Window manager client windows which is a subset of all windows that have been
+            // reparented i.e. new ids and don't map to the same ids as their all windows selves.
+            let reply = self
+                .conn
+                .get_property(false, self.root, self.atoms._NET_CLIENT_LIST, AtomEnum::WINDOW, 0, u32::MAX)?
+                .reply()?;
+            let children = reply.value32().ok_or(WmCtlError::PropertyNotFound("_NET_CLIENT_LIST".to_owned()))?;
+            children.collect::<Vec<_>>()
+        })
+    }
+
+    /// Get the window manager client windows in most recently used order
+    /// * Windows in the recorded active window history come first
+    /// * Remaining windows follow in _NET_CLIENT_LIST_STACKING order from top to bottom
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.windows_mru().unwrap()
+    /// ```
+    pub(crate) fn windows_mru(&self) -> WmCtlResult<Vec<u32>> {
+        // Defined as: _NET_CLIENT_LIST_STACKING, WINDOW[]/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_CLIENT_LIST_STACKING`
+        // request message with a `AtomEnum::WINDOW` type response and the windows will be in bottom to top order.
+        let reply = self
+            .conn
+            .get_property(false, self.root, self.atoms._NET_CLIENT_LIST_STACKING, AtomEnum::WINDOW, 0, u32::MAX)?
+            .reply()?;
+        let stacking = reply
+            .value32()
+            .ok_or(WmCtlError::PropertyNotFound("_NET_CLIENT_LIST_STACKING".to_owned()))?
+            .collect::<Vec<_>>();
+
+        // Drop closed windows from the history and fill in the rest from the stacking order
+        let mut windows = self.history()?.into_iter().filter(|x| stacking.contains(x)).collect::<Vec<_>>();
+        for id in stacking.into_iter().rev() {
+            if !windows.contains(&id) {
+                windows.push(id);
+            }
+        }
+        debug!("windows_mru: {:?}", windows);
+        Ok(windows)
+    }
+
+    /// Get window pid
+    /// * Falls back on the window's frame(s) then the window it is transient for if the window
+    ///   itself doesn't set _NET_WM_PID e.g. for apps with unusual window hierarchies
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_pid(1234)
+    /// ```
+    pub(crate) fn window_pid(&self, id: u32) -> WmCtlResult<i32> {
+        let mut pid = self.window_own_pid(id)?;
+
+        // Walk up at most two levels of frames before trying the transient for window
+        let mut parent = id;
+        for _ in 0..2 {
+            if pid.is_some() {
+                break;
+            }
+            parent = self.window_parent(parent)?;
+            if parent == self.root || parent == x11rb::NONE {
+                break;
+            }
+            pid = self.window_own_pid(parent)?;
+        }
+        if pid.is_none() {
+            let reply =
+                self.conn.get_property(false, id, AtomEnum::WM_TRANSIENT_FOR, AtomEnum::WINDOW, 0, 1)?.reply()?;
+            if let Some(owner) = reply.value32().and_then(|mut x| x.next()).filter(|&x| x != x11rb::NONE) {
+                pid = self.window_own_pid(owner)?;
+            }
+        }
+        let pid = pid.ok_or(WmCtlError::PropertyNotFound("_NET_WM_PID".to_owned()))?;
+        debug!("win_pid: id: {}, pid: {:?}", id, pid);
+        Ok(pid as i32)
+    }
+
+    // Get the _NET_WM_PID set directly on the window if any
+    fn window_own_pid(&self, id: u32) -> WmCtlResult<Option<u32>> {
+        // Defined as: _NET_WM_PID, CARDINAL/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_PID`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
+        // retrieve the values of which there will be a single value.
+        let reply =
+            self.conn.get_property(false, id, self.atoms._NET_WM_PID, AtomEnum::CARDINAL, 0, u32::MAX)?.reply()?;
+        Ok(reply.value32().and_then(|mut x| x.next()))
+    }
+
+    /// Get the timestamp of the last user interaction with the window
+    /// * Returns None if the window doesn't define a user time
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_user_time(1234)
+    /// ```
+    pub(crate) fn window_user_time(&self, id: u32) -> WmCtlResult<Option<u32>> {
+        // Defined as: _NET_WM_USER_TIME_WINDOW, WINDOW/32
+        // Clients may store the user time on a separate window to avoid waking up the window manager
+        // on every update in which case we need to read the user time from that window instead.
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._NET_WM_USER_TIME_WINDOW, AtomEnum::WINDOW, 0, u32::MAX)?
+            .reply()?;
+        let win = reply.value32().and_then(|mut x| x.next()).unwrap_or(id);
+
+        // Defined as: _NET_WM_USER_TIME, CARDINAL/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_USER_TIME`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
+        // retrieve the values of which there will be a single value.
+        let reply = self
+            .conn
+            .get_property(false, win, self.atoms._NET_WM_USER_TIME, AtomEnum::CARDINAL, 0, u32::MAX)?
+            .reply()?;
+        let time = reply.value32().and_then(|mut x| x.next());
+        debug!("win_user_time: id: {}, time: {:?}", id, time);
+        Ok(time)
+    }
+
+    /// Get the window's opacity as set for the compositing manager
+    /// * Returns None if the window doesn't define an opacity i.e. fully opaque
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to query
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_opacity(1234)
+    /// ```
+    pub(crate) fn window_opacity(&self, id: u32) -> WmCtlResult<Option<u32>> {
+        // Defined as: _NET_WM_WINDOW_OPACITY, CARDINAL/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_WINDOW_OPACITY`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
+        // retrieve the value where 0xFFFFFFFF is fully opaque.
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._NET_WM_WINDOW_OPACITY, AtomEnum::CARDINAL, 0, u32::MAX)?
+            .reply()?;
+        let opacity = reply.value32().and_then(|mut x| x.next());
+        debug!("win_opacity: id: {}, opacity: {:?}", id, opacity);
+        Ok(opacity)
+    }
+
+    /// Get the regions of the window that are fully opaque as set for the compositing manager
+    /// * Returns an empty list if the window doesn't define an opaque region
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to query
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_opaque_region(1234)
+    /// ```
+    pub(crate) fn window_opaque_region(&self, id: u32) -> WmCtlResult<Vec<(i32, i32, u32, u32)>> {
+        // Defined as: _NET_WM_OPAQUE_REGION, x, y, width, height, CARDINAL[][4]/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_OPAQUE_REGION`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
+        // retrieve the values in groups of four with the x, y coordinates relative to the window.
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._NET_WM_OPAQUE_REGION, AtomEnum::CARDINAL, 0, u32::MAX)?
+            .reply()?;
+        let values = reply.value32().map(|x| x.collect::<Vec<_>>()).unwrap_or_default();
+        let region = values.chunks_exact(4).map(|x| (x[0] as i32, x[1] as i32, x[2], x[3])).collect::<Vec<_>>();
+        debug!("win_opaque_region: id: {}, region: {:?}", id, region);
+        Ok(region)
+    }
+
+    /// Get the window's ICCCM size hints e.g. minimum and maximum sizes
+    /// * Returns None if the window doesn't define size hints
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to query
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_size_hints(1234)
+    /// ```
+    pub(crate) fn window_size_hints(&self, id: u32) -> WmCtlResult<Option<WmSizeHints>> {
+        // Defined as: WM_NORMAL_HINTS, WM_SIZE_HINTS/32
+        // which x11rb decodes for us as the fields are only valid when their flag bits are set.
+        let hints = WmSizeHints::get_normal_hints(&self.conn, id)?.reply()?;
+        debug!("win_size_hints: id: {}, hints: {:?}", id, hints);
+        Ok(hints)
+    }
+
+    /// Get window name
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_name(1234)
+    /// ```
+    pub(crate) fn window_name(&self, id: u32) -> WmCtlResult<String> {
+        // Defined as: _NET_WM_NAME, UTF8_STRING
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_NAME`
+        // request message with a `AtomEnum::UTF8_STRING` type response and we can use the `reply.value` accessor to
+        // retrieve the value.
+
+        // First try the _NET_WM_VISIBLE_NAME
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._NET_WM_VISIBLE_NAME, self.atoms.UTF8_STRING, 0, u32::MAX)?
+            .reply()?;
+        if reply.type_ != x11rb::NONE {
+            if let Ok(value) = str::from_utf8(&reply.value) {
+                if value != "" {
+                    debug!("win_name: using _NET_WM_VISIBLE_NAME for: {}", value);
+                    return Ok(value.to_owned());
+                }
+            }
+        }
+
+        // Next try the _NET_WM_NAME
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._NET_WM_NAME, self.atoms.UTF8_STRING, 0, u32::MAX)?
+            .reply()?;
+        if reply.type_ != x11rb::NONE {
+            if let Ok(value) = str::from_utf8(&reply.value) {
+                if value != "" {
+                    debug!("win_name: using _NET_WM_NAME for: {}", value);
+                    return Ok(value.to_owned());
+                }
+            }
+        }
+
+        // Fall back on the WM_NAME
+        let reply =
+            self.conn.get_property(false, id, AtomEnum::WM_NAME, AtomEnum::STRING, 0, u32::MAX)?.reply()?;
+        if reply.type_ != x11rb::NONE && !reply.value.is_empty() {
+            // STRING is ISO-8859-1 not UTF-8 so decode each byte as its unicode code point
+            let value = latin1_to_string(&reply.value);
+            debug!("win_name: using WM_NAME for: {}", value);
+            return Ok(value);
+        }
+
+        // No valid name was found
+        Err(WmCtlError::PropertyNotFound("_NET_WM_NAME | _WM_NAME".to_owned()).into())
+    }
+
+    /// Get window icon name i.e. the name shown when the window is iconified
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_icon_name(1234)
+    /// ```
+    pub(crate) fn window_icon_name(&self, id: u32) -> WmCtlResult<String> {
+        // Defined as: _NET_WM_ICON_NAME, UTF8_STRING
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_ICON_NAME`
+        // request message with a `AtomEnum::UTF8_STRING` type response and we can use the `reply.value` accessor to
+        // retrieve the value.
+
+        // First try the _NET_WM_VISIBLE_ICON_NAME
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._NET_WM_VISIBLE_ICON_NAME, self.atoms.UTF8_STRING, 0, u32::MAX)?
+            .reply()?;
+        if reply.type_ != x11rb::NONE {
+            if let Ok(value) = str::from_utf8(&reply.value) {
+                if !value.is_empty() {
+                    debug!("win_icon_name: using _NET_WM_VISIBLE_ICON_NAME for: {}", value);
+                    return Ok(value.to_owned());
+                }
+            }
+        }
+
+        // Next try the _NET_WM_ICON_NAME
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._NET_WM_ICON_NAME, self.atoms.UTF8_STRING, 0, u32::MAX)?
+            .reply()?;
+        if reply.type_ != x11rb::NONE {
+            if let Ok(value) = str::from_utf8(&reply.value) {
+                if !value.is_empty() {
+                    debug!("win_icon_name: using _NET_WM_ICON_NAME for: {}", value);
+                    return Ok(value.to_owned());
+                }
+            }
+        }
+
+        // Fall back on the WM_ICON_NAME
+        let reply =
+            self.conn.get_property(false, id, AtomEnum::WM_ICON_NAME, AtomEnum::STRING, 0, u32::MAX)?.reply()?;
+        if reply.type_ != x11rb::NONE && !reply.value.is_empty() {
+            // STRING is ISO-8859-1 not UTF-8 so decode each byte as its unicode code point
+            let value = latin1_to_string(&reply.value);
+            debug!("win_icon_name: using WM_ICON_NAME for: {}", value);
+            return Ok(value);
+        }
+
+        // No valid icon name was found
+        Err(WmCtlError::PropertyNotFound("_NET_WM_ICON_NAME | WM_ICON_NAME".to_owned()).into())
+    }
+
+    /// Get window instance and class from the WM_CLASS property in a single round trip
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let (instance, class) = wm.window_wm_class(1234).unwrap();
+    /// ```
+    pub(crate) fn window_wm_class(&self, id: u32) -> WmCtlResult<(String, String)> {
+        // Defined as: WM_CLASS, STRING
+        // which consists of two consecutive null terminated strings the first being the instance
+        // name and the second being the class name.
+        let reply =
+            self.conn.get_property(false, id, AtomEnum::WM_CLASS, AtomEnum::STRING, 0, u32::MAX)?.reply()?;
+        let (instance, class) =
+            split_wm_class(&reply.value).ok_or(WmCtlError::PropertyNotFound("WM_CLASS".to_owned()))?;
+        debug!("win_wm_class: id: {}, instance: {}, class: {}", id, instance, class);
+        Ok((instance, class))
+    }
+
+    /// Get window kind i.e. the first supported type in the window's preferred order
+    /// * Defaults to Normal if _NET_WM_WINDOW_TYPE isn't set or has no known types as per the EWMH spec
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_kind(1234)
+    /// ```
+    pub(crate) fn window_kind(&self, id: u32) -> WmCtlResult<Kind> {
+        let _kind = self.window_kinds(id)?.into_iter().next().unwrap_or(Kind::Normal);
+        debug!("win_kind: id: {}, kind: {:?}", id, _kind);
+        Ok(_kind)
+    }
+
+    /// Get all window kinds in the window's preferred order skipping unsupported types
+    /// * Defaults to Normal if _NET_WM_WINDOW_TYPE isn't set or has no known types as per the EWMH spec
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_kinds(1234)
+    /// ```
+    pub(crate) fn window_kinds(&self, id: u32) -> WmCtlResult<Vec<Kind>> {
+        // Defined as: _NET_WM_WINDOW_TYPE, ATOM[]/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_WINDOW_TYPE`
+        // request message with a `AtomEnum::ATOM` type response and we can use the `reply.value32()` accessor to
+        // retrieve the values.
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._NET_WM_WINDOW_TYPE, AtomEnum::ATOM, 0, u32::MAX)?
+            .reply()?;
+        let types = reply.value32().map(|x| x.collect::<Vec<_>>()).unwrap_or_default();
+        let kinds = Kind::from_all(&self.atoms, &types);
+        debug!("win_kinds: id: {}, kinds: {:?}", id, kinds);
+        Ok(kinds)
+    }
+
+    /// Get window state
+    /// * States that aren't part of the EWMH spec e.g. _KDE_NET_WM_STATE_* are skipped
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_state(1234)
+    /// ```
+    pub(crate) fn window_state(&self, id: u32) -> WmCtlResult<Vec<State>> {
+        let (states, unknown) = State::from_all(&self.atoms, &self.window_state_atoms(id)?);
+        debug!("win_state: id: {}, state: {:?}, skipped: {:?}", id, states, unknown);
+        Ok(states)
+    }
+
+    /// Get the names of the window states that aren't part of the EWMH spec e.g. _KDE_NET_WM_STATE_*
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_state_raw(1234)
+    /// ```
+    pub(crate) fn window_state_raw(&self, id: u32) -> WmCtlResult<Vec<String>> {
+        let (_, unknown) = State::from_all(&self.atoms, &self.window_state_atoms(id)?);
+        let names = self.atom_map(&unknown)?;
+        let names = unknown.iter().filter_map(|x| names.get(x).cloned()).collect::<Vec<_>>();
+        debug!("win_state_raw: id: {}, state: {:?}", id, names);
+        Ok(names)
+    }
+
+    /// Get the window state atoms
+    fn window_state_atoms(&self, id: u32) -> WmCtlResult<Vec<u32>> {
+        // Defined as: _NET_WM_STATE, ATOM[]
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_STATE`
+        // request message with a `AtomEnum::ATOM` type response and we can use the `reply.value32()` accessor to
+        // retrieve the values of which there may be multiple.
+        let reply =
+            self.conn.get_property(false, id, self.atoms._NET_WM_STATE, AtomEnum::ATOM, 0, u32::MAX)?.reply()?;
+        if reply.value_len == 0 {
+            return Ok(vec![]);
+        }
+        let atoms = reply.value32().ok_or(WmCtlError::PropertyNotFound("_NET_WM_STATE".to_owned()))?.collect();
+        Ok(atoms)
+    }
+
+    /// Get window parent
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_parent(1234)
+    /// ```
+    #[allow(dead_code)]
+    pub(crate) fn window_parent(&self, id: u32) -> WmCtlResult<u32> {
+        let tree = self.conn.query_tree(id)?.reply()?;
+        let parent_id = tree.parent;
+        debug!("win_parent: id: {}, parent: {:?}", id, parent_id);
+        Ok(parent_id)
+    }
+
+    /// Get window desktop
+    /// * Returns non zero based desktop number
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_desktop(1234)
+    /// ```
+    pub(crate) fn window_desktop(&self, id: u32) -> WmCtlResult<i32> {
+        // Defined as: _NET_WM_DESKTOP desktop, CARDINAL/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._NET_WM_DESKTOP`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the `reply.value32()` accessor to
+        // retrieve the values of which there will be a single value.
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._NET_WM_DESKTOP, AtomEnum::CARDINAL, 0, u32::MAX)?
+            .reply()?;
+        let mut desktop = reply.value32().and_then(|mut x| x.next()).map_or(-1, |x| x as i32);
+
+        // Offset to align with how desktops are typically numbered
+        if desktop != -1 {
+            desktop += 1;
+        }
+
+        debug!("win_desktop: id: {}, desktop: {}", id, desktop);
+        Ok(desktop as i32)
+    }
+
+    /// Ask the window manager to move the window to the given desktop
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    /// * `desktop` - zero based desktop number as defined by _NET_WM_DESKTOP
+    ///   or `ALL_DESKTOPS` to show the window on all desktops
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.set_window_desktop(1234, 0).unwrap();
+    /// ```
+    pub(crate) fn set_window_desktop(&self, id: u32, desktop: u32) -> WmCtlResult<()> {
+        self.send_event(ClientMessageEvent::new(
+            32,
+            id,
+            self.atoms._NET_WM_DESKTOP,
+            [desktop, self.source_indication, 0, 0, 0],
+        ))?;
+        debug!("set_win_desktop: id: {}, desktop: {}", id, desktop);
+        Ok(())
+    }
+
+    /// Get window visual geometry.
+    /// Geometry is a calculated value that represents the window's size and position including it's
+    /// frame or visually perceived frame. Be careful in calculating from this value as frame/application
+    /// borders are added and subtracted and positioning changed in different uses cases called out
+    /// below to make these values more intuitive visually. Other apps like xdotool or xwininfo use
+    /// the --frame option to include the window manager's frame in the calculation which is somewhat
+    /// akin to what is happending here only this also takes into account Client Side Decorations (CSD).
+    ///
+    /// * For Window Manager decorated windows this means this function is computing the window size
     ///   plus window manager's border decoration as this gives an intuitively understandable visual
     ///   window size on the screen. Positioning is also adjusted in this case to subtract the borders
     ///   for a total visual space on screen experience.
@@ -605,277 +1473,1016 @@ impl WinMgr {
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// let (x, y, w, h) = wm.window_geometry(1234).unwrap()
+    /// let (x, y, w, h) = wm.window_geometry(1234).unwrap()
+    /// ```
+    pub(crate) fn window_visual_geometry(&self, id: u32) -> WmCtlResult<(i32, i32, u32, u32)> {
+        Ok(self.window_geometries(id)?.1.into())
+    }
+
+    /// Get both the raw and visual window geometry from a single geometry fetch
+    /// * See window_geometry() and window_visual_geometry() for details on each
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Returns
+    /// * `(raw, visual)` the raw and visual geometry of the window
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let (raw, visual) = wm.window_geometries(1234).unwrap()
+    /// ```
+    pub(crate) fn window_geometries(&self, id: u32) -> WmCtlResult<(Geometry, Geometry)> {
+        let raw = self.window_geometry(id)?;
+        let gtk = self.window_gtk_borders(id).ok();
+        let frame = self.window_borders(id).ok();
+        let g = visual_geometry(raw, gtk, frame);
+        let (x, y, w, h) = (g.x, g.y, g.w, g.h);
+
+        debug!("win_geometry: id: {}, x: {}, y: {}, w: {}, h: {}", id, x, y, w, h);
+        Ok((raw.into(), g))
+    }
+
+    /// Get window geometry as reported by the window manager without any adjustments.
+    /// * This is the window's position and size without any window manager decorations included
+    ///   which can be counter intuitive visually if you're trying to understand the window's
+    ///   position and size on the screen.
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let (x, y, w, h) = wm.window_raw_geometry(1234).unwrap()
+    /// ```
+    pub(crate) fn window_geometry(&self, id: u32) -> WmCtlResult<(i32, i32, u32, u32)> {
+        // References
+        // * https://github.com/psychon/x11rb/blob/c55337f839fd03eeb77996b776a736fcf8136dd9/x11rb/examples/tutorial.rs#L1840
+        //
+        // The returned x, y location is relative to the upper-left corner of its parent window
+        // making the values completely useless as it doesn't relate to what we see on the screen
+        // in an intuitive way. In order to overcome this problem, we need to take a two-step approach.
+        // First, we find out the Id of the parent window, which might not be the root window. We then
+        // translate the relative coordinates in relation to the parent window to get actual screen
+        // coordinates that have real world meaning relative to 0, 0 of the screen.
+        let g = self.conn.get_geometry(id)?.reply()?;
+        let (w, h) = (g.width as u32, g.height as u32);
+
+        let mut parent = self.window_parent(id)?;
+        let (x, y) = if parent != self.root {
+            // NOTE: Despite the XCB directions to use the window's parent for the relative translation
+            // I've found in XFWM that this doesn't report the window's position correctly unless we
+            // always use the root window for the relative base to translate from.
+            parent = self.root;
+
+            // NOTE: Tests show that get_geometry returns the window's (x, y) coordinates with an offset
+            // taking into account the left and top borders of the window. For example a window that
+            // visually appears to be at (0, 0) on the screen but has borders of (4, 4, 28, 4) will have
+            // an (x, y) of (4, 28) returned from get_geometry. Additionally tests show that translate
+            // adds the same left and top border values. Thus as with [wmctrl] and other projects that
+            // input the get_geometry (x, y) values into translate function as is commonly shown in many
+            // tutorials the resulting (x, y) values are being incorrectly reported as (8, 56) instead of
+            // just (4, 28) as they are incorrectly geting double the left and right borders.
+            // Thus as done in the xwininfo project simply using (0, 0) as the input parameters for
+            // translate's (x, y) will correctly add only one set of borders to the (x, y) values.
+            let tx = self.conn.translate_coordinates(id, parent, 0, 0)?.reply()?;
+            (tx.dst_x as i32, tx.dst_y as i32)
+        } else {
+            // If parent is the root window then the x, y values are already correct
+            (g.x as i32, g.y as i32)
+        };
+
+        debug!("win_raw_geometry: id: {}, x: {}, y: {}, w: {}, h: {}", id, x, y, w, h);
+        Ok((x, y, w, h))
+    }
+
+    /// Get the visual geometry of all the given windows in a single batch of requests. Requests for
+    /// every window are sent before any replies are waited on such that the round trips overlap
+    /// rather than paying one per request per window as window_visual_geometry() does.
+    /// * Computes the same values as window_visual_geometry()
+    /// * Windows whose geometry can't be read e.g. having been closed are skipped
+    ///
+    /// ### Arguments
+    /// * `ids` - ids of the windows to get the geometry for
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let geometries = wm.windows_visual_geometry(&[1234, 5678]).unwrap();
+    /// ```
+    pub(crate) fn windows_visual_geometry(&self, ids: &[u32]) -> WmCtlResult<Vec<(u32, Geometry)>> {
+        // Send all requests up front
+        let mut cookies = vec![];
+        for &id in ids {
+            let extents = |atom| self.conn.get_property(false, id, atom, AtomEnum::CARDINAL, 0, 4);
+            cookies.push((
+                id,
+                self.conn.get_geometry(id)?,
+                self.conn.query_tree(id)?,
+                self.conn.translate_coordinates(id, self.root, 0, 0)?,
+                extents(self.atoms._GTK_FRAME_EXTENTS)?,
+                extents(self.atoms._NET_FRAME_EXTENTS)?,
+                extents(self.atoms._KDE_NET_WM_FRAME_STRUT)?,
+            ));
+        }
+
+        // Collect the replies computing the geometry as window_visual_geometry() does
+        let border = |reply: Option<GetPropertyReply>| {
+            let vals = reply?.value32()?.collect::<Vec<_>>();
+            (vals.len() == 4).then(|| Border::new(vals[0], vals[1], vals[2], vals[3]))
+        };
+        let mut geometries = vec![];
+        for (id, g, tree, tx, gtk, net, kde) in cookies {
+            let (Ok(g), Ok(tree), Ok(tx)) = (g.reply(), tree.reply(), tx.reply()) else {
+                continue;
+            };
+            let (x, y) = match tree.parent != self.root {
+                true => (tx.dst_x as i32, tx.dst_y as i32),
+                false => (g.x as i32, g.y as i32),
+            };
+            let raw = (x, y, g.width as u32, g.height as u32);
+            let frame = border(net.reply().ok()).or_else(|| border(kde.reply().ok()));
+            geometries.push((id, visual_geometry(raw, border(gtk.reply().ok()), frame)));
+        }
+        debug!("wins_visual_geometry: count: {}", geometries.len());
+        Ok(geometries)
+    }
+
+    /// Get window frame border values added by the window manager
+    /// * Falls back on _KDE_NET_WM_FRAME_STRUT if _NET_FRAME_EXTENTS isn't set e.g. for some KWin windows
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let win = window(12345);
+    /// let (l, r, t, b) = wm.window_borders().unwrap();
+    /// ```
+    pub(crate) fn window_borders(&self, id: u32) -> WmCtlResult<Border> {
+        // Window managers decorate windows with boarders and title bars. The _NET_FRAME_EXTENTS
+        // defined as: left, right, top, bottom, CARDINAL[4]/32 will retrieve these values via
+        // `get_property` api call with the use of the `self.atoms._NET_FRAME_EXTENTS`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the
+        // `reply.value32()`. KDE's _KDE_NET_WM_FRAME_STRUT shares the same layout.
+        //
+        // The standard property's error is reported if the fallback is missing as well.
+        let (l, r, t, b) =
+            self.window_frame_extents(id, self.atoms._NET_FRAME_EXTENTS, "_NET_FRAME_EXTENTS").or_else(|err| {
+                self.window_frame_extents(id, self.atoms._KDE_NET_WM_FRAME_STRUT, "_KDE_NET_WM_FRAME_STRUT")
+                    .map_err(|_| err)
+            })?;
+
+        debug!("win_borders: id: {}, l: {}, r: {}, t: {}, b: {}", id, l, r, t, b);
+        Ok(Border::new(l, r, t, b))
+    }
+
+    // Read the left, right, top, bottom CARDINAL[4]/32 frame extents from the given property
+    fn window_frame_extents(&self, id: u32, atom: u32, name: &str) -> WmCtlResult<(u32, u32, u32, u32)> {
+        let reply = self.conn.get_property(false, id, atom, AtomEnum::CARDINAL, 0, u32::MAX)?.reply()?;
+        let mut values = reply.value32().ok_or(WmCtlError::PropertyNotFound(name.to_owned()))?;
+        let l = values.next().ok_or(WmCtlError::PropertyNotFound(format!("{} left", name)))?;
+        let r = values.next().ok_or(WmCtlError::PropertyNotFound(format!("{} right", name)))?;
+        let t = values.next().ok_or(WmCtlError::PropertyNotFound(format!("{} top", name)))?;
+        let b = values.next().ok_or(WmCtlError::PropertyNotFound(format!("{} bottom", name)))?;
+        Ok((l, r, t, b))
+    }
+
+    /// Determine if this window is a GTK application
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let win = window(12345);
+    /// let result = win.window_is_gtk();
+    /// ```
+    pub(crate) fn window_is_gtk(&self, id: u32) -> bool {
+        if let Ok(b) = self.window_gtk_borders(id) {
+            return b.any();
+        }
+        false
+    }
+
+    /// Get GNOME window borders
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let win = window(12345);
+    /// let (l, r, t, b) = wm.window_gnome_borders().unwrap();
+    /// ```
+    #[allow(dead_code)]
+    pub(crate) fn window_gtk_borders(&self, id: u32) -> WmCtlResult<Border> {
+        // Window managers (a.k.a server-side) decorate windows with boarders and title bars. The
+        // _NET_FRAME_EXTENTS defined as: left, right, top, bottom, CARDINAL[4]/32 will retrieve
+        // these values via `get_property` api call with the use of the `self.atoms._NET_FRAME_EXTENTS`
+        // request message with a `AtomEnum::CARDINAL` type response and we can use the
+        // `reply.value32()`. Client-side decorations (CSD) is where the application draws the
+        // window decorations (borders, titlebar etc...) itself. In the CSD architecture used by GNOME
+        // the application draws decorations including the shadows. The shadows are click-through and
+        // semitransparent, but they are still part of the app window. To account for this the GNOME
+        // app will set the _GTK_FRAME_EXTENTS property showing the space consumed by these shadows that
+        // can be effectively used as the window borders rather than the window manager borders provided
+        // by _NET_FRAME_EXTENTS. _GTK_FRAME_EXTENTS is defined as: left, right, top, bottom
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._GTK_FRAME_EXTENTS, AtomEnum::CARDINAL, 0, u32::MAX)?
+            .reply()?;
+
+        // Don't abort if the property is not found as its not required
+        if reply.value.is_empty() {
+            return Ok(Border::default());
+        }
+
+        let mut values = reply.value32().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS".to_owned()))?;
+        let l = values.next().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS left".to_owned()))?;
+        let r = values.next().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS right".to_owned()))?;
+        let t = values.next().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS top".to_owned()))?;
+        let b = values.next().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS bottom".to_owned()))?;
+
+        debug!("win_gnome_borders: id: {}, l: {}, r: {}, t: {}, b: {}", id, l, r, t, b);
+        Ok(Border::new(l, r, t, b))
+    }
+
+    /// Get all properties for the given window as a sorted list
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to pull properteries for
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_properties(1234).unwrap();
+    /// ```
+    pub(crate) fn window_properties(&self, id: u32) -> WmCtlResult<Vec<crate::Property>> {
+        let reply = self.conn.list_properties(id)?.reply()?;
+
+        // Get atoms names
+        let atom_map = self.atom_map(&reply.atoms)?;
+
+        // Create properties from the atoms and sort by name
+        let mut props = atom_map.iter().map(|x| crate::Property::new(*x.0, x.1)).collect::<Vec<_>>();
+        props.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Faster and more efficient to send all requests before calling reply()
+        let cookies = props
+            .iter()
+            .map(|x| self.conn.get_property(false, id, x.id, AtomEnum::ANY, 0, u32::MAX))
+            .collect::<Vec<_>>();
+        for (cookie, prop) in cookies.into_iter().zip(props.iter_mut()) {
+            prop.value = self.property_value(&cookie?.reply()?)?;
+        }
+        debug!("win_properties: id: {}, count: {}", id, props.len());
+        Ok(props)
+    }
+
+    // Decode the property's value according to its type similar to xprop i.e. ATOM values are
+    // resolved to names, WINDOW values are ids, CARDINAL and INTEGER values are numbers and string
+    // values are quoted text. Long values e.g. _NET_WM_ICON are truncated.
+    fn property_value(&self, reply: &GetPropertyReply) -> WmCtlResult<String> {
+        const MAX_VALUES: usize = 32;
+        let typ = reply.type_;
+        let values32 = || reply.value32().map(|x| x.collect::<Vec<_>>()).unwrap_or_default();
+        let values = if let Some(text) = self.property_text(reply) {
+            text.split('\0').map(|x| format!("\"{}\"", x)).collect::<Vec<_>>()
+        } else if typ == u32::from(AtomEnum::ATOM) {
+            let atoms = values32();
+            let names = self.atom_map(&atoms.iter().copied().filter(|x| *x != x11rb::NONE).collect::<Vec<_>>())?;
+            atoms.iter().map(|x| names.get(x).cloned().unwrap_or_else(|| x.to_string())).collect()
+        } else if typ == u32::from(AtomEnum::INTEGER) {
+            values32().iter().map(|x| (*x as i32).to_string()).collect()
+        } else {
+            // WINDOW, CARDINAL and any other types are shown as numbers
+            match reply.format {
+                8 => reply.value8().map(|x| x.map(|x| x.to_string()).collect()).unwrap_or_default(),
+                16 => reply.value16().map(|x| x.map(|x| x.to_string()).collect()).unwrap_or_default(),
+                _ => values32().iter().map(|x| x.to_string()).collect(),
+            }
+        };
+
+        let mut value = values.iter().take(MAX_VALUES).cloned().collect::<Vec<_>>().join(", ");
+        if values.len() > MAX_VALUES {
+            value += &format!(", ... ({} values)", values.len());
+        }
+        Ok(value)
+    }
+
+    /// Get the 32 bit values of the named window property
+    /// * Returns None if the atom doesn't exist or the window doesn't define the property
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    /// * `name` - name of the property e.g. _NET_WM_PID
+    /// * `kind` - expected type of the property e.g. AtomEnum::CARDINAL
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_property32(1234, "_NET_WM_PID", AtomEnum::CARDINAL).unwrap();
+    /// ```
+    pub(crate) fn window_property32(&self, id: u32, name: &str, kind: AtomEnum) -> WmCtlResult<Option<Vec<u32>>> {
+        let values = self
+            .window_property(id, name, kind)?
+            .filter(|x| x.type_ == u32::from(kind))
+            .and_then(|x| x.value32().map(|x| x.collect::<Vec<_>>()));
+        debug!("win_property32: id: {}, name: {}, values: {:?}", id, name, values);
+        Ok(values)
+    }
+
+    /// Get the string value of the named window property
+    /// * Returns None if the atom doesn't exist or the window doesn't define the property
+    /// * Supports both the STRING and UTF8_STRING types decoding STRING values as Latin-1
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    /// * `name` - name of the property e.g. _NET_WM_NAME
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_property_string(1234, "_NET_WM_NAME").unwrap();
+    /// ```
+    pub(crate) fn window_property_string(&self, id: u32, name: &str) -> WmCtlResult<Option<String>> {
+        let value = self.window_property(id, name, AtomEnum::ANY)?.and_then(|x| self.property_text(&x));
+        debug!("win_property_string: id: {}, name: {}, value: {:?}", id, name, value);
+        Ok(value)
+    }
+
+    // Decode the property's text according to its type i.e. STRING values are Latin-1 and
+    // UTF8_STRING values are UTF-8 with trailing nulls removed
+    // * Returns None if the property isn't a string type
+    fn property_text(&self, reply: &GetPropertyReply) -> Option<String> {
+        let text = if reply.type_ == u32::from(AtomEnum::STRING) {
+            latin1_to_string(&reply.value)
+        } else if reply.type_ == self.atoms.UTF8_STRING {
+            String::from_utf8_lossy(&reply.value).into_owned()
+        } else {
+            return None;
+        };
+        Some(text.trim_end_matches('\0').to_owned())
+    }
+
+    /// Get the named window property by interning the atom by name
+    /// * Returns None if the atom doesn't exist or the window doesn't define the property
+    fn window_property(&self, id: u32, name: &str, kind: AtomEnum) -> WmCtlResult<Option<GetPropertyReply>> {
+        let atom = self.conn.intern_atom(true, name.as_bytes())?.reply()?.atom;
+        if atom == x11rb::NONE {
+            return Ok(None);
+        }
+        let reply = self.conn.get_property(false, id, atom, kind, 0, u32::MAX)?.reply()?;
+        Ok(if reply.type_ == x11rb::NONE { None } else { Some(reply) })
+    }
+
+    /// Get the window's Motif hints
+    /// * Returns the default hints i.e. all decorations if the window doesn't define any
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to query
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let hints = wm.window_motif_hints(1234).unwrap();
+    /// ```
+    pub(crate) fn window_motif_hints(&self, id: u32) -> WmCtlResult<MotifHints> {
+        // Defined as: _MOTIF_WM_HINTS, _MOTIF_WM_HINTS/32
+        // which means when retrieving the value via `get_property` that we need to use a `self.atoms._MOTIF_WM_HINTS`
+        // request message with a `self.atoms._MOTIF_WM_HINTS` type response and we can use the `reply.value32()`
+        // accessor to retrieve the five values.
+        let reply = self
+            .conn
+            .get_property(false, id, self.atoms._MOTIF_WM_HINTS, self.atoms._MOTIF_WM_HINTS, 0, 5)?
+            .reply()?;
+        let hints = MotifHints::from(&reply.value32().map(|x| x.collect::<Vec<_>>()).unwrap_or_default());
+        debug!("win_motif_hints: id: {}, hints: {:?}", id, hints);
+        Ok(hints)
+    }
+
+    /// Set the window's Motif hints
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    /// * `hints` - hints to set
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.set_window_motif_hints(1234, &MotifHints::default()).unwrap();
+    /// ```
+    pub(crate) fn set_window_motif_hints(&self, id: u32, hints: &MotifHints) -> WmCtlResult<()> {
+        self.conn.change_property32(
+            PropMode::REPLACE,
+            id,
+            self.atoms._MOTIF_WM_HINTS,
+            self.atoms._MOTIF_WM_HINTS,
+            &hints.to_vec(),
+        )?;
+        self.conn.flush()?;
+        debug!("set_win_motif_hints: id: {}, hints: {:?}", id, hints);
+        Ok(())
+    }
+
+    /// Start watching the named property of the window for changes. The returned watch uses its own
+    /// connection to the X server so that waiting on it neither consumes events from nor changes the
+    /// event mask of this connection and doesn't need this connection to be held while waiting.
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to watch
+    /// * `name` - name of the property to watch e.g. _NET_FRAME_EXTENTS
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let watch = wm.watch_window_property(1234, "_NET_FRAME_EXTENTS").unwrap();
+    /// watch.wait(Duration::from_secs(1)).unwrap();
+    /// ```
+    pub(crate) fn watch_window_property(&self, id: u32, name: &str) -> WmCtlResult<PropertyWatch> {
+        let atom = self.conn.intern_atom(false, name.as_bytes())?.reply()?.atom;
+        debug!("watch_win_property: id: {}, name: {}", id, name);
+        PropertyWatch::new(self.display.as_deref(), id, vec![atom])
+    }
+
+    /// Start watching the window's title i.e. _NET_WM_VISIBLE_NAME, _NET_WM_NAME or WM_NAME for
+    /// changes using its own connection to the X server as watch_window_property() does
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to watch
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let watch = wm.watch_window_title(1234).unwrap();
+    /// watch.wait(Duration::from_secs(1)).unwrap();
+    /// ```
+    pub(crate) fn watch_window_title(&self, id: u32) -> WmCtlResult<PropertyWatch> {
+        let atoms = vec![
+            self.atoms._NET_WM_VISIBLE_NAME,
+            self.atoms._NET_WM_NAME,
+            AtomEnum::WM_NAME.into(),
+        ];
+        debug!("watch_win_title: id: {}", id);
+        PropertyWatch::new(self.display.as_deref(), id, atoms)
+    }
+
+    /// Subscribe to property change notifications on the root window which is where the window
+    /// manager publishes changes to the active window, desktops and client list.
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.watch_root().unwrap();
+    /// ```
+    pub(crate) fn watch_root(&self) -> WmCtlResult<()> {
+        self.conn
+            .change_window_attributes(
+                self.root,
+                &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE),
+            )?
+            .check()?;
+        debug!("watch_root: root: {}", self.root);
+        Ok(())
+    }
+
+    /// Block until the window manager raises the next event of interest
+    /// * Requires `watch_root` to have been called first to receive root property notifications
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.watch_root().unwrap();
+    /// let event = wm.next_event().unwrap();
+    /// ```
+    pub(crate) fn next_event(&self) -> WmCtlResult<WmEvent> {
+        loop {
+            if let Some(event) = self.translate_event(self.conn.wait_for_event()?)? {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Get the next event of interest without blocking by reading whatever the X server has
+    /// already sent over the connection
+    /// * Requires `watch_root` to have been called first to receive root property notifications
+    ///
+    /// ### Returns
+    /// * the next event of interest or None if no more events are ready
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.watch_root().unwrap();
+    /// let event = wm.poll_event().unwrap();
+    /// ```
+    #[cfg(feature = "async")]
+    pub(crate) fn poll_event(&self) -> WmCtlResult<Option<WmEvent>> {
+        while let Some(event) = self.conn.poll_for_event()? {
+            if let Some(event) = self.translate_event(event)? {
+                return Ok(Some(event));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get the file descriptor of the connection to the X server for readiness polling
+    #[cfg(feature = "async")]
+    pub(crate) fn conn_fd(&self) -> std::os::fd::BorrowedFd<'_> {
+        use std::os::fd::AsFd;
+        self.conn.stream().as_fd()
+    }
+
+    // Translate the X event into a window manager event if it is one of interest
+    fn translate_event(&self, event: Event) -> WmCtlResult<Option<WmEvent>> {
+        let Event::PropertyNotify(e) = event else {
+            return Ok(None);
+        };
+        if e.window != self.root || e.state != xproto::Property::NEW_VALUE {
+            return Ok(None);
+        }
+        let event = if e.atom == self.atoms._NET_ACTIVE_WINDOW {
+            let id = self.active_window().unwrap_or(0);
+            self.record_active(id);
+            WmEvent::ActiveWindow(id)
+        } else if e.atom == self.atoms._NET_CURRENT_DESKTOP {
+            WmEvent::Desktop(self.current_desktop()?)
+        } else if e.atom == self.atoms._NET_NUMBER_OF_DESKTOPS {
+            WmEvent::DesktopCount(self.desktops()?)
+        } else if e.atom == self.atoms._NET_SHOWING_DESKTOP {
+            WmEvent::ShowingDesktop(self.showing_desktop()?)
+        } else if e.atom == self.atoms._NET_CLIENT_LIST {
+            WmEvent::ClientList
+        } else if e.atom == self.atoms._NET_WORKAREA {
+            WmEvent::WorkArea
+        } else {
+            return Ok(None);
+        };
+        debug!("next_event: {}", event);
+        Ok(Some(event))
+    }
+
+    /// Capture the visible portion of the window as an image
+    /// * Returns a `NotViewable` error if the window isn't viewable or is entirely off screen
+    /// * Portions of the window that are off screen are clipped as the X server rejects them
+    /// * Only 32 bits per pixel formats e.g. 24 and 32 bit depths are supported
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to capture
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let image = wm.window_capture(1234).unwrap();
+    /// ```
+    pub(crate) fn window_capture(&self, id: u32) -> WmCtlResult<Image> {
+        // get_image fails for windows that aren't viewable e.g. minimized or on another desktop
+        if self.window_attributes(id)? != crate::MapState::Viewable {
+            return Err(WmCtlError::NotViewable(id).into());
+        }
+
+        // Clip the window to the screen as get_image fails for any portion off screen
+        let (x, y, w, h) = self.window_geometry(id)?;
+        let screen = Geometry::new(0, 0, self.width, self.height);
+        let clip = Geometry::new(x, y, w, h).intersect(&screen).ok_or(WmCtlError::NotViewable(id))?;
+
+        // Request the pixels relative to the window's origin
+        let reply = self
+            .conn
+            .get_image(
+                ImageFormat::Z_PIXMAP,
+                id,
+                (clip.x - x) as i16,
+                (clip.y - y) as i16,
+                clip.w as u16,
+                clip.h as u16,
+                !0,
+            )?
+            .reply()?;
+
+        // Determine the pixel layout the server used for the depth of the window
+        let setup = self.conn.setup();
+        let bpp =
+            setup.pixmap_formats.iter().find(|x| x.depth == reply.depth).map(|x| x.bits_per_pixel).unwrap_or(0);
+        if bpp != 32 {
+            return Err(WmCtlError::Unsupported(format!("capturing {} bits per pixel", bpp)).into());
+        }
+
+        // Convert the 32 bit pixels into RGBA respecting the server's byte order
+        let lsb = setup.image_byte_order == ImageOrder::LSB_FIRST;
+        let mut data = Vec::with_capacity(reply.data.len());
+        for px in reply.data.chunks_exact(4) {
+            let (a, r, g, b) = if lsb { (px[3], px[2], px[1], px[0]) } else { (px[0], px[1], px[2], px[3]) };
+            data.extend_from_slice(&[r, g, b, if reply.depth == 32 { a } else { 0xff }]);
+        }
+
+        debug!("win_capture: id: {}, w: {}, h: {}, depth: {}", id, clip.w, clip.h, reply.depth);
+        Ok(Image {
+            width: clip.w,
+            height: clip.h,
+            depth: reply.depth,
+            data,
+        })
+    }
+
+    /// Get the window's map state as tracked by the X server
+    /// * Valid for any window whether managed by the window manager or not
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let state = wm.window_attributes(12345).unwrap();
+    /// ```
+    pub(crate) fn window_attributes(&self, id: u32) -> WmCtlResult<crate::MapState> {
+        let attr = self.conn.get_window_attributes(id)?.reply()?;
+        debug!(
+            "win_attributes: id: {}, win_gravity: {:?}, bit_gravity: {:?}",
+            id, attr.win_gravity, attr.bit_gravity
+        );
+        //Ok((Class::from(attr.class.into())?, crate::MapState::from(attr.map_state.into())?))
+        Ok(crate::MapState::from(attr.map_state.into())?)
+    }
+
+    /// Get the X window class of the window i.e. InputOutput or InputOnly
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// let class = wm.window_class_type(12345).unwrap();
+    /// ```
+    pub(crate) fn window_class_type(&self, id: u32) -> WmCtlResult<WinClass> {
+        let attr = self.conn.get_window_attributes(id)?.reply()?;
+        debug!("win_class_type: id: {}, class: {:?}", id, attr.class);
+        WinClass::from(attr.class.into())
+    }
+
+    /// Determine if the window has override redirect set i.e. it bypasses the window manager
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.window_is_override_redirect(12345).unwrap();
+    /// ```
+    pub(crate) fn window_is_override_redirect(&self, id: u32) -> WmCtlResult<bool> {
+        let attr = self.conn.get_window_attributes(id)?.reply()?;
+        debug!("win_override_redirect: id: {}, override_redirect: {}", id, attr.override_redirect);
+        Ok(attr.override_redirect)
+    }
+
+    /// Map the window on the screen
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.map_window().unwrap();
+    /// ```
+    pub(crate) fn map_window(&self, id: u32) -> WmCtlResult<()> {
+        debug!("map_window: id: {}", id);
+        self.conn.map_window(id)?;
+        Ok(())
+    }
+
+    /// Restack the window above its siblings
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.raise_window(1234).unwrap();
+    /// ```
+    pub(crate) fn raise_window(&self, id: u32) -> WmCtlResult<()> {
+        self.conn.configure_window(id, &ConfigureWindowAux::new().stack_mode(StackMode::ABOVE))?;
+        self.conn.flush()?;
+        debug!("raise: id: {}", id);
+        Ok(())
+    }
+
+    /// Restack the window below all its siblings
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.lower_window(1234).unwrap();
+    /// ```
+    pub(crate) fn lower_window(&self, id: u32) -> WmCtlResult<()> {
+        self.conn.configure_window(id, &ConfigureWindowAux::new().stack_mode(StackMode::BELOW))?;
+        self.conn.flush()?;
+        debug!("lower: id: {}", id);
+        Ok(())
+    }
+
+    /// Restack the window directly above or below the given sibling window
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    /// * `sibling` - id of the sibling window to stack relative to
+    /// * `mode` - StackMode::ABOVE or StackMode::BELOW the sibling
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.restack_window(1234, 5678, StackMode::ABOVE).unwrap();
+    /// ```
+    pub(crate) fn restack_window(&self, id: u32, sibling: u32, mode: StackMode) -> WmCtlResult<()> {
+        self.conn.configure_window(id, &ConfigureWindowAux::new().sibling(sibling).stack_mode(mode))?;
+        self.conn.flush()?;
+        debug!("restack: id: {}, sibling: {}, mode: {:?}", id, sibling, mode);
+        Ok(())
+    }
+
+    /// Restack the window above its siblings and give it the input focus. Both requests are sent
+    /// before flushing so they arrive at the X server together.
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.raise_and_focus_window(1234).unwrap();
+    /// ```
+    pub(crate) fn raise_and_focus_window(&self, id: u32) -> WmCtlResult<()> {
+        self.conn.configure_window(id, &ConfigureWindowAux::new().stack_mode(StackMode::ABOVE))?;
+        self.conn.set_input_focus(InputFocus::PARENT, id, x11rb::CURRENT_TIME)?;
+        self.conn.flush()?;
+        debug!("raise_and_focus: id: {}", id);
+        Ok(())
+    }
+
+    /// Set the X server border width of the window
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    /// * `width` - border width in pixels
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.set_window_border_width(1234, 2).unwrap();
+    /// ```
+    pub(crate) fn set_window_border_width(&self, id: u32, width: u32) -> WmCtlResult<()> {
+        self.conn.configure_window(id, &ConfigureWindowAux::new().border_width(width))?;
+        self.conn.flush()?;
+        debug!("set_border_width: id: {}, width: {}", id, width);
+        Ok(())
+    }
+
+    /// Maximize the window both horizontally and vertically
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.maximize_window().unwrap();
+    /// ```
+    pub(crate) fn maximize_window(&self, id: u32) -> WmCtlResult<()> {
+        self.set_window_state(
+            id,
+            WINDOW_STATE_ACTION_ADD,
+            self.atoms._NET_WM_STATE_MAXIMIZED_HORZ,
+            self.atoms._NET_WM_STATE_MAXIMIZED_VERT,
+        )?;
+        debug!("maximize: id: {}", id);
+        Ok(())
+    }
+
+    /// Remove the MaxVert and MaxHorz states
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.unmaximize_window().unwrap();
     /// ```
-    pub(crate) fn window_visual_geometry(&self, id: u32) -> WmCtlResult<(i32, i32, u32, u32)> {
-        let (mut x, mut y, mut w, mut h) = self.window_geometry(id)?;
-
-        // Account for CSD borders
-        let mut is_gtk = false;
-        if let Ok(b) = self.window_gtk_borders(id) {
-            if b.any() {
-                w = w - b.l - b.r;
-                h = h - b.t - b.b;
-                x = x + b.l as i32;
-                y = y + b.t as i32;
-                is_gtk = true;
-            }
-        }
-        if !is_gtk {
-            if let Ok(b) = self.window_borders(id) {
-                w = w + b.l + b.r;
-                h = h + b.t + b.b;
-                x = x - b.l as i32;
-                y = y - b.t as i32;
-            }
-        }
-
-        debug!("win_geometry: id: {}, x: {}, y: {}, w: {}, h: {}", id, x, y, w, h);
-        Ok((x, y, w, h))
+    pub(crate) fn unmaximize_window(&self, id: u32) -> WmCtlResult<()> {
+        self.set_window_state(
+            id,
+            WINDOW_STATE_ACTION_REMOVE,
+            self.atoms._NET_WM_STATE_MAXIMIZED_HORZ,
+            self.atoms._NET_WM_STATE_MAXIMIZED_VERT,
+        )?;
+        debug!("unmaximize: id: {}", id);
+        Ok(())
     }
 
-    /// Get window geometry as reported by the window manager without any adjustments.
-    /// * This is the window's position and size without any window manager decorations included
-    ///   which can be counter intuitive visually if you're trying to understand the window's
-    ///   position and size on the screen.
+    /// Add the given state to the window
     ///
     /// ### Arguments
     /// * `id` - id of the window to manipulate
+    /// * `state` - state to add
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// let (x, y, w, h) = wm.window_raw_geometry(1234).unwrap()
+    /// wm.add_window_state(1234, &State::Above).unwrap();
     /// ```
-    pub(crate) fn window_geometry(&self, id: u32) -> WmCtlResult<(i32, i32, u32, u32)> {
-        // References
-        // * https://github.com/psychon/x11rb/blob/c55337f839fd03eeb77996b776a736fcf8136dd9/x11rb/examples/tutorial.rs#L1840
-        //
-        // The returned x, y location is relative to the upper-left corner of its parent window
-        // making the values completely useless as it doesn't relate to what we see on the screen
-        // in an intuitive way. In order to overcome this problem, we need to take a two-step approach.
-        // First, we find out the Id of the parent window, which might not be the root window. We then
-        // translate the relative coordinates in relation to the parent window to get actual screen
-        // coordinates that have real world meaning relative to 0, 0 of the screen.
-        let g = self.conn.get_geometry(id)?.reply()?;
-        let (w, h) = (g.width as u32, g.height as u32);
-
-        let mut parent = self.window_parent(id)?.id;
-        let (x, y) = if parent != self.root {
-            // NOTE: Despite the XCB directions to use the window's parent for the relative translation
-            // I've found in XFWM that this doesn't report the window's position correctly unless we
-            // always use the root window for the relative base to translate from.
-            parent = self.root;
-
-            // NOTE: Tests show that get_geometry returns the window's (x, y) coordinates with an offset
-            // taking into account the left and top borders of the window. For example a window that
-            // visually appears to be at (0, 0) on the screen but has borders of (4, 4, 28, 4) will have
-            // an (x, y) of (4, 28) returned from get_geometry. Additionally tests show that translate
-            // adds the same left and top border values. Thus as with [wmctrl] and other projects that
-            // input the get_geometry (x, y) values into translate function as is commonly shown in many
-            // tutorials the resulting (x, y) values are being incorrectly reported as (8, 56) instead of
-            // just (4, 28) as they are incorrectly geting double the left and right borders.
-            // Thus as done in the xwininfo project simply using (0, 0) as the input parameters for
-            // translate's (x, y) will correctly add only one set of borders to the (x, y) values.
-            let tx = self.conn.translate_coordinates(id, parent, 0, 0)?.reply()?;
-            (tx.dst_x as i32, tx.dst_y as i32)
-        } else {
-            // If parent is the root window then the x, y values are already correct
-            (g.x as i32, g.y as i32)
-        };
-
-        debug!("win_raw_geometry: id: {}, x: {}, y: {}, w: {}, h: {}", id, x, y, w, h);
-        Ok((x, y, w, h))
+    pub(crate) fn add_window_state(&self, id: u32, state: &State) -> WmCtlResult<()> {
+        self.set_window_state(id, WINDOW_STATE_ACTION_ADD, state.atom(&self.atoms)?, 0)?;
+        debug!("add_win_state: id: {}, state: {}", id, state);
+        Ok(())
     }
 
-    /// Get window frame border values added by the window manager
+    /// Remove the given state from the window
     ///
     /// ### Arguments
     /// * `id` - id of the window to manipulate
+    /// * `state` - state to remove
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// let win = window(12345);
-    /// let (l, r, t, b) = wm.window_borders().unwrap();
+    /// wm.remove_window_state(1234, &State::Above).unwrap();
     /// ```
-    pub(crate) fn window_borders(&self, id: u32) -> WmCtlResult<Border> {
-        // Window managers decorate windows with boarders and title bars. The _NET_FRAME_EXTENTS
-        // defined as: left, right, top, bottom, CARDINAL[4]/32 will retrieve these values via
-        // `get_property` api call with the use of the `self.atoms._NET_FRAME_EXTENTS`
-        // request message with a `AtomEnum::CARDINAL` type response and we can use the
-        // `reply.value32()`.
-        let reply = self
-            .conn
-            .get_property(false, id, self.atoms._NET_FRAME_EXTENTS, AtomEnum::CARDINAL, 0, u32::MAX)?
-            .reply()?;
-        let mut values = reply.value32().ok_or(WmCtlError::PropertyNotFound("_NET_FRAME_EXTENTS".to_owned()))?;
-        let l = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_FRAME_EXTENTS left".to_owned()))?;
-        let r = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_FRAME_EXTENTS right".to_owned()))?;
-        let t = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_FRAME_EXTENTS top".to_owned()))?;
-        let b = values.next().ok_or(WmCtlError::PropertyNotFound("_NET_FRAME_EXTENTS bottom".to_owned()))?;
-
-        debug!("win_borders: id: {}, l: {}, r: {}, t: {}, b: {}", id, l, r, t, b);
-        Ok(Border::new(l, r, t, b))
+    pub(crate) fn remove_window_state(&self, id: u32, state: &State) -> WmCtlResult<()> {
+        self.set_window_state(id, WINDOW_STATE_ACTION_REMOVE, state.atom(&self.atoms)?, 0)?;
+        debug!("remove_win_state: id: {}, state: {}", id, state);
+        Ok(())
     }
 
-    /// Determine if this window is a GTK application
+    /// Check that the window manager supports the given state as advertised by _NET_SUPPORTED
+    /// * Returns an `Unsupported` error naming the state's atom if it isn't supported
+    ///
+    /// ### Arguments
+    /// * `state` - state to check
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// let win = window(12345);
-    /// let result = win.window_is_gtk();
+    /// wm.check_state_supported(&State::Shaded).unwrap();
     /// ```
-    pub(crate) fn window_is_gtk(&self, id: u32) -> bool {
-        if let Ok(b) = self.window_gtk_borders(id) {
-            return b.any();
-        }
-        false
+    pub(crate) fn check_state_supported(&self, state: &State) -> WmCtlResult<()> {
+        state.check_supported(&self.atoms, &self.supported)
     }
 
-    /// Get GNOME window borders
+    /// Toggle the given state on the window without first reading the window's current states
     ///
     /// ### Arguments
     /// * `id` - id of the window to manipulate
+    /// * `state` - state to toggle
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// let win = window(12345);
-    /// let (l, r, t, b) = wm.window_gnome_borders().unwrap();
+    /// wm.toggle_window_state(1234, &State::Fullscreen).unwrap();
     /// ```
-    #[allow(dead_code)]
-    pub(crate) fn window_gtk_borders(&self, id: u32) -> WmCtlResult<Border> {
-        // Window managers (a.k.a server-side) decorate windows with boarders and title bars. The
-        // _NET_FRAME_EXTENTS defined as: left, right, top, bottom, CARDINAL[4]/32 will retrieve
-        // these values via `get_property` api call with the use of the `self.atoms._NET_FRAME_EXTENTS`
-        // request message with a `AtomEnum::CARDINAL` type response and we can use the
-        // `reply.value32()`. Client-side decorations (CSD) is where the application draws the
-        // window decorations (borders, titlebar etc...) itself. In the CSD architecture used by GNOME
-        // the application draws decorations including the shadows. The shadows are click-through and
-        // semitransparent, but they are still part of the app window. To account for this the GNOME
-        // app will set the _GTK_FRAME_EXTENTS property showing the space consumed by these shadows that
-        // can be effectively used as the window borders rather than the window manager borders provided
-        // by _NET_FRAME_EXTENTS. _GTK_FRAME_EXTENTS is defined as: left, right, top, bottom
-        let reply = self
-            .conn
-            .get_property(false, id, self.atoms._GTK_FRAME_EXTENTS, AtomEnum::CARDINAL, 0, u32::MAX)?
-            .reply()?;
-
-        // Don't abort if the property is not found as its not required
-        if reply.value.is_empty() {
-            return Ok(Border::default());
-        }
-
-        let mut values = reply.value32().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS".to_owned()))?;
-        let l = values.next().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS left".to_owned()))?;
-        let r = values.next().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS right".to_owned()))?;
-        let t = values.next().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS top".to_owned()))?;
-        let b = values.next().ok_or(WmCtlError::PropertyNotFound("_GTK_FRAME_EXTENTS bottom".to_owned()))?;
-
-        debug!("win_gnome_borders: id: {}, l: {}, r: {}, t: {}, b: {}", id, l, r, t, b);
-        Ok(Border::new(l, r, t, b))
+    pub(crate) fn toggle_window_state(&self, id: u32, state: &State) -> WmCtlResult<()> {
+        self.set_window_state(id, WINDOW_STATE_ACTION_TOGGLE, state.atom(&self.atoms)?, 0)?;
+        debug!("toggle_win_state: id: {}, state: {}", id, state);
+        Ok(())
     }
 
-    /// Get all properties for the given window as a sorted list
+    /// Ask the window manager to activate the window i.e. switch to its desktop, raise and focus it
     ///
     /// ### Arguments
-    /// * `id` - id of the window to pull properteries for
+    /// * `id` - id of the window to manipulate
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.window_properties(1234).unwrap();
+    /// wm.activate_window(1234).unwrap();
     /// ```
-    pub(crate) fn window_properties(&self, id: u32) -> WmCtlResult<Vec<crate::Property>> {
-        let reply = self.conn.list_properties(id)?.reply()?;
-
-        // Get atoms names
-        let atom_map = self.atom_map(&reply.atoms)?;
-
-        // Create properties from the atoms and sort by name
-        let mut props = atom_map.iter().map(|x| crate::Property::new(*x.0, x.1)).collect::<Vec<_>>();
-        props.sort_by(|a, b| a.name.cmp(&b.name));
-        // for prop in props.iter() {
-        //     let reply = self.conn.get_property(false, id, prop.id, AtomEnum::CARDINAL, 0, u32::MAX)?.reply()?;
-        // }
-        Ok(props)
+    pub(crate) fn activate_window(&self, id: u32) -> WmCtlResult<()> {
+        // Data is the source indication, the timestamp of the user action and the currently active
+        // window. Using CURRENT_TIME i.e. 0 as there is no user event to take a timestamp from.
+        let active = self.active_window().unwrap_or(0);
+        self.send_event(ClientMessageEvent::new(
+            32,
+            id,
+            self.atoms._NET_ACTIVE_WINDOW,
+            [self.source_indication, 0, active, 0, 0],
+        ))?;
+        debug!("activate: id: {}", id);
+        Ok(())
     }
 
-    /// Get window attribrtes
+    /// Add or remove the SkipTaskbar state to hide the window from or show it in the taskbar
     ///
     /// ### Arguments
     /// * `id` - id of the window to manipulate
+    /// * `enable` - true to hide the window from the taskbar, false to show it
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// let (class, state) = wm.win_attributes(12345).unwrap();
+    /// wm.set_window_skip_taskbar(1234, true).unwrap();
     /// ```
-    #[allow(dead_code)]
-    pub(crate) fn window_attributes(&self, id: u32) -> WmCtlResult<crate::MapState> {
-        let attr = self.conn.get_window_attributes(id)?.reply()?;
-        debug!(
-            "win_attributes: id: {}, win_gravity: {:?}, bit_gravity: {:?}",
-            id, attr.win_gravity, attr.bit_gravity
-        );
-        //Ok((Class::from(attr.class.into())?, crate::MapState::from(attr.map_state.into())?))
-        Ok(crate::MapState::from(attr.map_state.into())?)
+    pub(crate) fn set_window_skip_taskbar(&self, id: u32, enable: bool) -> WmCtlResult<()> {
+        if !self.is_supported(self.atoms._NET_WM_STATE_SKIP_TASKBAR) {
+            return Err(WmCtlError::Unsupported("_NET_WM_STATE_SKIP_TASKBAR".to_owned()).into());
+        }
+        let action = if enable { WINDOW_STATE_ACTION_ADD } else { WINDOW_STATE_ACTION_REMOVE };
+        self.set_window_state(id, action, self.atoms._NET_WM_STATE_SKIP_TASKBAR, 0)?;
+        debug!("set_skip_taskbar: id: {}, enable: {}", id, enable);
+        Ok(())
     }
 
-    /// Map the window on the screen
+    /// Add or remove the SkipPager state to hide the window from or show it in the pager
     ///
     /// ### Arguments
     /// * `id` - id of the window to manipulate
+    /// * `enable` - true to hide the window from the pager, false to show it
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.map_window().unwrap();
+    /// wm.set_window_skip_pager(1234, true).unwrap();
     /// ```
-    pub(crate) fn map_window(&self, id: u32) -> WmCtlResult<()> {
-        debug!("map_window: id: {}", id);
-        self.conn.map_window(id)?;
+    pub(crate) fn set_window_skip_pager(&self, id: u32, enable: bool) -> WmCtlResult<()> {
+        if !self.is_supported(self.atoms._NET_WM_STATE_SKIP_PAGER) {
+            return Err(WmCtlError::Unsupported("_NET_WM_STATE_SKIP_PAGER".to_owned()).into());
+        }
+        let action = if enable { WINDOW_STATE_ACTION_ADD } else { WINDOW_STATE_ACTION_REMOVE };
+        self.set_window_state(id, action, self.atoms._NET_WM_STATE_SKIP_PAGER, 0)?;
+        debug!("set_skip_pager: id: {}, enable: {}", id, enable);
         Ok(())
     }
 
-    /// Maximize the window both horizontally and vertically
+    /// Hand off an interactive move or resize of the window to the window manager starting at the
+    /// current pointer position as if the left button was pressed there
     ///
     /// ### Arguments
     /// * `id` - id of the window to manipulate
+    /// * `edge` - direction of the move or resize
     ///
     /// ### Examples
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.maximize_window().unwrap();
+    /// wm.move_resize_interactive(1234, ResizeEdge::Move).unwrap();
     /// ```
-    pub(crate) fn maximize_window(&self, id: u32) -> WmCtlResult<()> {
+    pub(crate) fn move_resize_interactive(&self, id: u32, edge: ResizeEdge) -> WmCtlResult<()> {
+        // Data is the pointer's root position, direction, button and source indication
+        let (x, y) = self.pointer()?;
         self.send_event(ClientMessageEvent::new(
             32,
             id,
-            self.atoms._NET_WM_STATE,
-            [
-                WINDOW_STATE_ACTION_ADD,
-                self.atoms._NET_WM_STATE_MAXIMIZED_HORZ,
-                self.atoms._NET_WM_STATE_MAXIMIZED_VERT,
-                0,
-                0,
-            ],
+            self.atoms._NET_WM_MOVERESIZE,
+            [x as u32, y as u32, edge.into(), 1, self.source_indication],
         ))?;
-        debug!("maximize: id: {}", id);
+        debug!("move_resize_interactive: id: {}, edge: {}, x: {}, y: {}", id, edge, x, y);
         Ok(())
     }
 
-    /// Remove the MaxVert and MaxHorz states
+    /// Add the Sticky and Above states to pin the window on all desktops on top of other windows
     ///
     /// ### Arguments
     /// * `id` - id of the window to manipulate
@@ -884,26 +2491,73 @@ impl WinMgr {
     /// ```ignore
     /// use libwmctl::prelude::*;
     /// let wm = WinMgr::connect().unwrap();
-    /// wm.unmaximize_window().unwrap();
+    /// wm.pin_window(1234).unwrap();
     /// ```
-    pub(crate) fn unmaximize_window(&self, id: u32) -> WmCtlResult<()> {
+    pub(crate) fn pin_window(&self, id: u32) -> WmCtlResult<()> {
+        self.set_window_state(
+            id,
+            WINDOW_STATE_ACTION_ADD,
+            self.atoms._NET_WM_STATE_STICKY,
+            self.atoms._NET_WM_STATE_ABOVE,
+        )?;
+        debug!("pin: id: {}", id);
+        Ok(())
+    }
+
+    /// Remove the Sticky and Above states to unpin the window
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.unpin_window(1234).unwrap();
+    /// ```
+    pub(crate) fn unpin_window(&self, id: u32) -> WmCtlResult<()> {
+        self.set_window_state(
+            id,
+            WINDOW_STATE_ACTION_REMOVE,
+            self.atoms._NET_WM_STATE_STICKY,
+            self.atoms._NET_WM_STATE_ABOVE,
+        )?;
+        debug!("unpin: id: {}", id);
+        Ok(())
+    }
+
+    /// Show the window menu of the GTK client side decorated window at the given position
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    /// * `x` - x root coordinate to show the menu at
+    /// * `y` - y root coordinate to show the menu at
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.show_window_menu(1234, 100, 100).unwrap();
+    /// ```
+    pub(crate) fn show_window_menu(&self, id: u32, x: i32, y: i32) -> WmCtlResult<()> {
+        if !self.window_is_gtk(id) {
+            return Err(WmCtlError::Unsupported("_GTK_SHOW_WINDOW_MENU for non GTK windows".to_owned()).into());
+        }
+
+        // Defined as: _GTK_SHOW_WINDOW_MENU, device id, x root, y root
+        // Using device id 0 as we aren't responding to a specific input device's event
         self.send_event(ClientMessageEvent::new(
             32,
             id,
-            self.atoms._NET_WM_STATE,
-            [
-                WINDOW_STATE_ACTION_REMOVE,
-                self.atoms._NET_WM_STATE_MAXIMIZED_HORZ,
-                self.atoms._NET_WM_STATE_MAXIMIZED_VERT,
-                0,
-                0,
-            ],
+            self.atoms._GTK_SHOW_WINDOW_MENU,
+            [0, x as u32, y as u32, 0, 0],
         ))?;
-        debug!("unmaximize: id: {}", id);
+        debug!("show_window_menu: id: {}, x: {}, y: {}", id, x, y);
         Ok(())
     }
 
     /// Move and resize window
+    /// * Returns an `InvalidGeometry` error for zero sizes or values outside the X11 coordinate range
     ///
     /// ### Arguments
     /// * `id` - id of the window to manipulate
@@ -925,6 +2579,15 @@ impl WinMgr {
     pub(crate) fn move_resize_window(
         &self, id: u32, gravity: Option<u32>, x: Option<i32>, y: Option<i32>, w: Option<u32>, h: Option<u32>,
     ) -> WmCtlResult<()> {
+        // X11 stores coordinates as 16 bit signed values and sizes as 16 bit unsigned values though
+        // sizes beyond the signed range or zero sized windows are always a sign of a caller bug.
+        let coord = |v: i32| v < i16::MIN as i32 || v > i16::MAX as i32;
+        let size = |v: u32| v == 0 || v > i16::MAX as u32;
+        if x.is_some_and(coord) || y.is_some_and(coord) || w.is_some_and(size) || h.is_some_and(size) {
+            let err = format!("x: {:?}, y: {:?}, w: {:?}, h: {:?}", x, y, w, h);
+            return Err(WmCtlError::InvalidGeometry(err).into());
+        }
+
         self.conn.configure_window(id, &ConfigureWindowAux::new().width(w).height(h).x(x).y(y))?;
         self.conn.flush()?; // Requires the flush to work
 
@@ -971,6 +2634,48 @@ impl WinMgr {
         Ok(())
     }
 
+    /// Send a _NET_WM_STATE client message to add, remove or toggle up to two states at once. The
+    /// EWMH spec allows two properties per message so both states are changed atomically.
+    /// * Toggling lets the window manager flip the state without it first being read which avoids
+    ///   racing with other changes to the window's states
+    ///
+    /// ### Arguments
+    /// * `id` - id of the window to manipulate
+    /// * `action` - one of WINDOW_STATE_ACTION_REMOVE, WINDOW_STATE_ACTION_ADD or WINDOW_STATE_ACTION_TOGGLE
+    /// * `first` - first state atom to change
+    /// * `second` - second state atom to change or 0 to only change the first
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.set_window_state(1234, WINDOW_STATE_ACTION_TOGGLE, wm.atoms._NET_WM_STATE_FULLSCREEN, 0).unwrap();
+    /// ```
+    pub(crate) fn set_window_state(
+        &self, id: u32, action: WindowStateAction, first: u32, second: u32,
+    ) -> WmCtlResult<()> {
+        self.send_event(ClientMessageEvent::new(
+            32,
+            id,
+            self.atoms._NET_WM_STATE,
+            [action, first, second, self.source_indication, 0],
+        ))
+    }
+
+    /// Wait for the X server to process all pending requests
+    ///
+    /// ### Examples
+    /// ```ignore
+    /// use libwmctl::prelude::*;
+    /// let wm = WinMgr::connect().unwrap();
+    /// wm.sync().unwrap();
+    /// ```
+    pub(crate) fn sync(&self) -> WmCtlResult<()> {
+        self.conn.sync()?;
+        debug!("sync");
+        Ok(())
+    }
+
     /// Send the event ensuring that a flush is called and that the message was precisely
     /// executed in the case of a resize/move.
     ///
@@ -1010,3 +2715,124 @@ impl WinMgr {
         println!("DataType: {:?}", AtomEnum::from(reply.type_ as u8));
     }
 }
+
+// Decode the given ISO-8859-1 (Latin-1) bytes e.g. from a STRING property. Latin-1 maps each byte
+// directly to the unicode code point of the same value.
+fn latin1_to_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|&x| x as char).collect()
+}
+
+// Compute the visual geometry of a window from its raw geometry and borders. CSD windows report
+// their shadows via _GTK_FRAME_EXTENTS which are removed, otherwise the window manager's frame
+// borders are added.
+fn visual_geometry(raw: (i32, i32, u32, u32), gtk: Option<Border>, frame: Option<Border>) -> Geometry {
+    let (mut x, mut y, mut w, mut h) = raw;
+    match gtk.filter(|b| b.any()) {
+        Some(b) => {
+            w = w.saturating_sub(b.w());
+            h = h.saturating_sub(b.h());
+            x += b.l as i32;
+            y += b.t as i32;
+        },
+        None => {
+            if let Some(b) = frame {
+                w += b.w();
+                h += b.h();
+                x -= b.l as i32;
+                y -= b.t as i32;
+            }
+        },
+    }
+    Geometry::new(x, y, w, h)
+}
+
+/// PropertyWatch receives property change notifications for a window over its own connection to
+/// the X server. The notifications stop when the watch is dropped and its connection closed.
+pub(crate) struct PropertyWatch {
+    conn: RustConnection, // connection dedicated to the watch
+    id: u32,              // window being watched
+    atoms: Vec<u32>,      // properties being watched
+}
+
+impl PropertyWatch {
+    /// Connect to the display and subscribe to property change notifications for the window
+    ///
+    /// ### Arguments
+    /// * `display` - X11 display to connect to or None for the `DISPLAY` environment variable
+    /// * `id` - id of the window to watch
+    /// * `atoms` - properties to watch
+    fn new(display: Option<&str>, id: u32, atoms: Vec<u32>) -> WmCtlResult<Self> {
+        let (conn, _) = x11rb::connect(display)?;
+        conn.change_window_attributes(
+            id,
+            &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE),
+        )?
+        .check()?;
+        Ok(Self { conn, id, atoms })
+    }
+
+    /// Wait until any of the watched properties is set or changed
+    ///
+    /// ### Arguments
+    /// * `timeout` - how long to wait before giving up
+    ///
+    /// ### Returns
+    /// * true if a property was set or changed, false if the timeout expired first
+    pub(crate) fn wait(&self, timeout: Duration) -> WmCtlResult<bool> {
+        let deadline = Instant::now() + timeout;
+        let mut changed = false;
+        while !changed && Instant::now() < deadline {
+            match self.conn.poll_for_event()? {
+                Some(Event::PropertyNotify(e)) => {
+                    changed = e.window == self.id
+                        && self.atoms.contains(&e.atom)
+                        && e.state == xproto::Property::NEW_VALUE;
+                },
+                Some(_) => (),
+                None => thread::sleep(Duration::from_millis(10)),
+            }
+        }
+        debug!("property_watch: id: {}, changed: {}", self.id, changed);
+        Ok(changed)
+    }
+}
+
+// Split the WM_CLASS value into its instance and class names
+// * Falls back on the instance name when the class name is missing
+// * Returns None when the value is empty
+fn split_wm_class(value: &[u8]) -> Option<(String, String)> {
+    let mut iter = value.split(|x| *x == 0).filter(|x| !x.is_empty());
+    let instance = latin1_to_string(iter.next()?);
+    let class = iter.next().map_or(instance.clone(), latin1_to_string);
+    Some((instance, class))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_wm_class() {
+        assert_eq!(split_wm_class(b"navigator\0Firefox\0"), Some(("navigator".to_owned(), "Firefox".to_owned())));
+        assert_eq!(split_wm_class(b"xterm\0"), Some(("xterm".to_owned(), "xterm".to_owned())));
+        assert_eq!(split_wm_class(b"xterm"), Some(("xterm".to_owned(), "xterm".to_owned())));
+        assert_eq!(split_wm_class(b"\0\0"), None);
+        assert_eq!(split_wm_class(b""), None);
+    }
+
+    #[test]
+    fn test_latin1_to_string() {
+        assert_eq!(latin1_to_string(b"Caf\xe9"), "Café");
+    }
+
+    #[test]
+    fn test_visual_geometry() {
+        let raw = (100, 100, 800, 600);
+        let frame = || Some(Border::new(4, 4, 28, 4));
+        let gtk = || Some(Border::new(10, 10, 5, 15));
+        assert_eq!(visual_geometry(raw, None, None), Geometry::new(100, 100, 800, 600));
+        assert_eq!(visual_geometry(raw, None, frame()), Geometry::new(96, 72, 808, 632));
+        assert_eq!(visual_geometry(raw, gtk(), frame()), Geometry::new(110, 105, 780, 580));
+        assert_eq!(visual_geometry(raw, Some(Border::default()), frame()), Geometry::new(96, 72, 808, 632));
+    }
+}
@@ -0,0 +1,246 @@
+//! End to end tests against a headless Xvfb X server
+//!
+//! A minimal window manager is emulated in a background thread that publishes the EWMH root
+//! properties libwmctl requires, manages mapped windows and honors configure and state requests.
+//! * Requires `Xvfb` to be installed and is ignored by default, run it with
+//!   `cargo test -p libwmctl --test xvfb -- --ignored` which fails if `Xvfb` can't be started
+use libwmctl::prelude::*;
+use std::{
+    env,
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::{Arc, RwLock},
+    thread,
+    time::{Duration, Instant},
+};
+use x11rb::{
+    connection::Connection,
+    protocol::xproto::{
+        AtomEnum, ChangeWindowAttributesAux, ConfigureWindowAux, ConnectionExt as _, EventMask, PropMode,
+        WindowClass,
+    },
+    protocol::Event,
+    rust_connection::RustConnection,
+    wrapper::ConnectionExt as _,
+};
+
+// How long to wait for the emulated window manager to apply a request
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Xvfb server that is killed when dropped
+struct Xvfb {
+    child: Child,
+    display: String,
+}
+
+impl Xvfb {
+    /// Start Xvfb on the first free display
+    /// * Returns an error if Xvfb isn't installed or exits before reporting its display
+    fn start() -> Result<Self, String> {
+        let mut child = Command::new("Xvfb")
+            .args(["-displayfd", "1", "-screen", "0", "1280x1024x24", "-nolisten", "tcp"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| format!("failed to start Xvfb: {}", err))?;
+
+        // Xvfb writes the display number it chose once it is ready for connections
+        let mut line = String::new();
+        let _ = BufReader::new(child.stdout.take().unwrap()).read_line(&mut line);
+        let xvfb = Self {
+            child,
+            display: format!(":{}", line.trim()),
+        };
+        match line.trim().parse::<u32>() {
+            Ok(_) => Ok(xvfb),
+            Err(_) => Err(format!("Xvfb exited without reporting its display: {:?}", line)),
+        }
+    }
+}
+
+impl Drop for Xvfb {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Intern the given atom
+fn atom(conn: &RustConnection, name: &str) -> u32 {
+    conn.intern_atom(false, name.as_bytes()).unwrap().reply().unwrap().atom
+}
+
+/// Set the given 32 bit property values on the window
+fn set32(conn: &RustConnection, win: u32, name: &str, typ: impl Into<u32>, values: &[u32]) {
+    conn.change_property32(PropMode::REPLACE, win, atom(conn, name), typ, values).unwrap();
+}
+
+/// Emulate a minimal EWMH window manager on the given display until the process exits
+fn run_winmgr(display: &str) {
+    let (conn, screen) = x11rb::connect(Some(display)).unwrap();
+    let (root, width, height) = {
+        let screen = &conn.setup().roots[screen];
+        (screen.root, screen.width_in_pixels as u32, screen.height_in_pixels as u32)
+    };
+
+    // Publish the window manager's identity and root properties
+    let check = conn.generate_id().unwrap();
+    conn.create_window(0, check, root, 0, 0, 1, 1, 0, WindowClass::INPUT_OUTPUT, 0, &Default::default()).unwrap();
+    let utf8 = atom(&conn, "UTF8_STRING");
+    conn.change_property8(PropMode::REPLACE, check, atom(&conn, "_NET_WM_NAME"), utf8, b"wmctl-test").unwrap();
+    set32(&conn, check, "_NET_SUPPORTING_WM_CHECK", AtomEnum::WINDOW, &[check]);
+    set32(&conn, root, "_NET_SUPPORTING_WM_CHECK", AtomEnum::WINDOW, &[check]);
+    set32(&conn, root, "_NET_NUMBER_OF_DESKTOPS", AtomEnum::CARDINAL, &[1]);
+    set32(&conn, root, "_NET_CURRENT_DESKTOP", AtomEnum::CARDINAL, &[0]);
+    set32(&conn, root, "_NET_WORKAREA", AtomEnum::CARDINAL, &[0, 0, width, height]);
+    set32(&conn, root, "_NET_CLIENT_LIST", AtomEnum::WINDOW, &[]);
+    let net_wm_state = atom(&conn, "_NET_WM_STATE");
+    let supported = ["_NET_WM_STATE", "_NET_WM_STATE_MAXIMIZED_HORZ", "_NET_WM_STATE_MAXIMIZED_VERT"];
+    set32(&conn, root, "_NET_SUPPORTED", AtomEnum::ATOM, &supported.map(|x| atom(&conn, x)));
+
+    // Redirect requests for top level windows to the emulated window manager
+    let mask = EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY;
+    conn.change_window_attributes(root, &ChangeWindowAttributesAux::new().event_mask(mask))
+        .unwrap()
+        .check()
+        .unwrap();
+    conn.sync().unwrap();
+
+    let mut clients = vec![];
+    while let Ok(event) = conn.wait_for_event() {
+        match event {
+            Event::MapRequest(e) => {
+                conn.map_window(e.window).unwrap();
+                clients.push(e.window);
+                set32(&conn, root, "_NET_CLIENT_LIST", AtomEnum::WINDOW, &clients);
+                set32(&conn, root, "_NET_ACTIVE_WINDOW", AtomEnum::WINDOW, &[e.window]);
+                set32(&conn, e.window, "_NET_WM_DESKTOP", AtomEnum::CARDINAL, &[0]);
+            },
+            Event::ConfigureRequest(e) => {
+                conn.configure_window(e.window, &ConfigureWindowAux::from_configure_request(&e)).unwrap();
+            },
+            Event::ClientMessage(e) if e.type_ == net_wm_state => {
+                // Defined as: action, first property, second property, source indication
+                let data = e.data.as_data32();
+                let reply = conn.get_property(false, e.window, net_wm_state, AtomEnum::ATOM, 0, 1024).unwrap();
+                let mut states =
+                    reply.reply().unwrap().value32().map(|x| x.collect::<Vec<_>>()).unwrap_or_default();
+                for state in [data[1], data[2]].into_iter().filter(|&x| x != x11rb::NONE) {
+                    let present = states.contains(&state);
+                    match data[0] {
+                        WINDOW_STATE_ACTION_ADD if !present => states.push(state),
+                        WINDOW_STATE_ACTION_TOGGLE if !present => states.push(state),
+                        WINDOW_STATE_ACTION_REMOVE | WINDOW_STATE_ACTION_TOGGLE => states.retain(|&x| x != state),
+                        _ => (),
+                    }
+                }
+                set32(&conn, e.window, "_NET_WM_STATE", AtomEnum::ATOM, &states);
+            },
+            _ => (),
+        }
+        conn.flush().unwrap();
+    }
+}
+
+/// Create a client window with the given name optionally mapping it
+fn create_client(conn: &RustConnection, screen: usize, name: &str, map: bool) -> u32 {
+    let root = conn.setup().roots[screen].root;
+    let win = conn.generate_id().unwrap();
+    conn.create_window(0, win, root, 0, 0, 200, 100, 0, WindowClass::INPUT_OUTPUT, 0, &Default::default())
+        .unwrap();
+    let utf8 = atom(conn, "UTF8_STRING");
+    conn.change_property8(PropMode::REPLACE, win, atom(conn, "_NET_WM_NAME"), utf8, name.as_bytes()).unwrap();
+    conn.change_property8(PropMode::REPLACE, win, AtomEnum::WM_NAME, AtomEnum::STRING, name.as_bytes()).unwrap();
+    if map {
+        conn.map_window(win).unwrap();
+    }
+    conn.sync().unwrap();
+    win
+}
+
+/// Poll the given condition until it is true or the timeout expires
+fn wait_until(mut f: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + TIMEOUT;
+    while Instant::now() < deadline {
+        if f() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+#[test]
+#[ignore = "requires Xvfb, run with --ignored"]
+fn test_xvfb() {
+    let xvfb = Xvfb::start().unwrap_or_else(|err| panic!("{}", err));
+
+    // Global functions operate against the display given by the environment which is set before
+    // any other threads are running
+    env::set_var("DISPLAY", &xvfb.display);
+    let display = xvfb.display.clone();
+    thread::spawn(move || run_winmgr(&display));
+
+    // Wait for the emulated window manager to publish its identity
+    let (app, screen) = x11rb::connect(Some(&xvfb.display)).unwrap();
+    let check = atom(&app, "_NET_SUPPORTING_WM_CHECK");
+    let root = app.setup().roots[screen].root;
+    assert!(wait_until(|| {
+        let reply = app.get_property(false, root, check, AtomEnum::WINDOW, 0, 1).unwrap().reply().unwrap();
+        reply.value32().and_then(|mut x| x.next()).is_some()
+    }));
+    let id = create_client(&app, screen, "wmctl test", true);
+    let unmapped = create_client(&app, screen, "wmctl unmapped", false);
+
+    // Operate through a connection that isn't bound to the global singleton
+    let wm = Arc::new(RwLock::new(WinMgr::new_connection(Some(&xvfb.display)).unwrap()));
+    let win = Window::with_winmgr(&wm, id);
+    assert!(wait_until(|| win.is_visible().unwrap_or(false)));
+    assert_eq!(win.name().unwrap(), "wmctl test");
+    assert_eq!(win.mapped().unwrap(), MapState::Viewable);
+
+    // A freshly created window that was never mapped reports unmapped
+    assert_eq!(Window::with_winmgr(&wm, unmapped).mapped().unwrap(), MapState::Unmapped);
+
+    // Crate level functions operate on the explicit connection
+    assert!(libwmctl::windows_on(&wm, false).unwrap().iter().any(|x| x.id == id));
+    assert_eq!(libwmctl::active_on(&wm).unwrap().id, id);
+    assert_eq!(libwmctl::info_on(&wm).unwrap().name, "wmctl-test");
+
+    // Move and resize the window
+    win.move_resize(10, 20, 300, 200).unwrap();
+    assert!(wait_until(|| win.geometry().is_ok_and(|x| x == (10, 20, 300, 200))));
+    assert!(win.placement_matches(&Geometry::new(10, 20, 300, 200), 0).unwrap());
+    assert!(!win.placement_matches(&Geometry::new(40, 20, 300, 200), 8).unwrap());
+
+    // Maximize and unmaximize the window
+    win.maximize().unwrap();
+    assert!(wait_until(|| win.maximized()));
+    win.unmaximize().unwrap();
+    assert!(wait_until(|| !win.maximized()));
+    assert!(win.maximize_verified().unwrap());
+    assert!(win.unmaximize_verified().unwrap());
+
+    // Global functions operate against the display given by the environment
+    assert!(libwmctl::windows(false).unwrap().iter().any(|x| x.id == id));
+    assert!(!libwmctl::windows(false).unwrap().iter().any(|x| x.id == unmapped));
+    let all = libwmctl::windows(true).unwrap();
+    assert!(all.iter().any(|x| x.id == unmapped && x.mapped().is_ok_and(|x| x == MapState::Unmapped)));
+    assert!(all.iter().any(|x| x.id == id && x.mapped().is_ok_and(|x| x == MapState::Viewable)));
+    assert_eq!(libwmctl::active().id, id);
+    assert_eq!(window(id).name().unwrap(), "wmctl test");
+
+    // Mapping a new window raises a client list change
+    let mut watcher = libwmctl::watch().unwrap();
+    create_client(&app, screen, "wmctl watched", true);
+    assert!(matches!(watcher.next(), Some(Ok(WmEvent::ClientList))));
+    #[cfg(feature = "async")]
+    {
+        use futures_core::Stream;
+        use std::{future, pin::Pin};
+        let mut stream = libwmctl::watch_stream().unwrap();
+        create_client(&app, screen, "wmctl streamed", true);
+        let event = async_io::block_on(future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)));
+        assert!(matches!(event, Some(Ok(WmEvent::ClientList))));
+    }
+}
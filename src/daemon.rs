@@ -0,0 +1,239 @@
+//! Daemon mode keeps a single connection to the X server open and accepts commands over a Unix
+//! socket such that rapid hotkey driven commands e.g. `wmctl shape grow` don't pay the cost of
+//! connecting and caching atoms on every invocation.
+//!
+//! While running it also watches the window manager recording active window changes in the
+//! history used by `wmctl cycle`.
+//!
+//! ### Socket protocol
+//! * The socket is `$XDG_RUNTIME_DIR/wmctl.sock` falling back on `/tmp/wmctl-$USER.sock`
+//! * Each connection carries a single request and response
+//! * A request is a single line of the client's `DISPLAY`, empty if unset, followed by the
+//!   command's arguments, excluding the binary name, separated by tabs e.g. `:0\tshape\tgrow\n`
+//!   or `:0\t-c\tfirefox\tplace\tsmall\tcenter\n`
+//! * The response is a single line of either `ok` or `error: <message>` followed by the output
+//!   the command printed, if any, until the connection is closed
+//! * Requests for a different display than the daemon's are answered with a single `mismatch`
+//!   line without being executed
+//! * Commands are executed one at a time in the order they are received
+//! * Clients that don't send their request within a few seconds are dropped
+//!
+//! ### Forwarding
+//! Normal invocations forward their arguments to the daemon when it is running and fall back on
+//! executing directly otherwise. Every command is forwarded as the output of forwarded commands is
+//! sent back and written to the invoking process's stdout. Commands for a different display than
+//! the daemon is connected to e.g. `DISPLAY=:1 wmctl shape grow` are executed directly.
+use clap::App;
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    os::{
+        fd::{AsFd, AsRawFd, RawFd},
+        unix::{
+            ffi::{OsStrExt, OsStringExt},
+            net::{UnixListener, UnixStream},
+        },
+    },
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+use tracing::{debug, info};
+use witcher::prelude::*;
+
+// How long a client has to send its request before it is dropped
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Response to requests for a different display than the daemon's
+const MISMATCH: &str = "mismatch";
+
+/// Get the path of the daemon's socket
+pub fn socket_path() -> PathBuf {
+    match env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join("wmctl.sock"),
+        None => env::temp_dir().join(format!("wmctl-{}.sock", env::var("USER").unwrap_or_default())),
+    }
+}
+
+/// Forward the command to the daemon if it is running rather than executing it directly
+/// * Returns None if the daemon isn't running, is connected to a different display or the command
+///   can't be forwarded
+/// * The command's output is written to stdout
+///
+/// ### Arguments
+/// * `args` - the command's arguments excluding the binary name
+pub fn forward(args: &[OsString]) -> Option<Result<()>> {
+    if args.iter().any(|x| x.as_bytes().iter().any(|&b| b == b'\t' || b == b'\n')) {
+        return None;
+    }
+    let display = env::var_os("DISPLAY").unwrap_or_default();
+    if display.as_bytes().iter().any(|&b| b == b'\t' || b == b'\n') {
+        return None;
+    }
+    let mut stream = UnixStream::connect(socket_path()).ok()?;
+    debug!("daemon: forwarding: {:?}", args);
+    let response = request(&mut stream, &display, args);
+    if response.as_ref().is_ok_and(|x| x == MISMATCH) {
+        debug!("daemon: display mismatch, executing directly");
+        return None;
+    }
+    Some(response.pass().and_then(|response| match response.strip_prefix("error: ") {
+        Some(err) => Err(io::Error::other(err.to_owned())).pass(),
+        None => Ok(()),
+    }))
+}
+
+/// Send the request to the daemon, copy the command's output to stdout and read back the response
+///
+/// ### Arguments
+/// * `stream` - the daemon connection
+/// * `display` - the client's `DISPLAY`
+/// * `args` - the command's arguments excluding the binary name
+fn request(stream: &mut UnixStream, display: &OsStr, args: &[OsString]) -> io::Result<String> {
+    let fields = std::iter::once(display).chain(args.iter().map(|x| x.as_os_str()));
+    let mut line = fields.map(|x| x.as_bytes()).collect::<Vec<_>>().join(&b'\t');
+    line.push(b'\n');
+    stream.write_all(&line)?;
+
+    let mut reader = BufReader::new(stream);
+    let mut response = String::new();
+    reader.read_line(&mut response)?;
+    io::copy(&mut reader, &mut io::stdout().lock())?;
+    Ok(response.trim_end().to_owned())
+}
+
+/// Run the daemon accepting commands on the socket until killed
+///
+/// ### Arguments
+/// * `app` - the App definition used to parse the received commands
+pub fn run(app: &App) -> Result<()> {
+    let path = socket_path();
+
+    // Remove the socket left behind by a previous daemon unless it is still running
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            return Err(io::Error::other(format!("daemon is already running: {}", path.display()))).pass();
+        }
+        fs::remove_file(&path).pass()?;
+    }
+    let listener = UnixListener::bind(&path).pass()?;
+    info!("daemon listening on: {}", path.display());
+
+    // Watching records every active window change in the history used by `wmctl cycle`
+    thread::spawn(|| match libwmctl::watch() {
+        Ok(watcher) => for _ in watcher {},
+        Err(err) => debug!("daemon: failed to watch the window manager: {}", err),
+    });
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(err) = handle(app, stream) {
+                    debug!("daemon: failed to handle request: {}", err);
+                }
+            },
+            Err(err) => debug!("daemon: failed to accept connection: {}", err),
+        }
+    }
+    Ok(())
+}
+
+/// Handle a single request writing the response and the command's output back to the client
+///
+/// ### Arguments
+/// * `app` - the App definition used to parse the received command
+/// * `stream` - the client connection
+fn handle(app: &App, mut stream: UnixStream) -> io::Result<()> {
+    // Bound how long a client can hold up the daemon's single request at a time processing
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut line = vec![];
+    BufReader::new(&stream).read_until(b'\n', &mut line)?;
+    while line.last().is_some_and(|&x| x == b'\n' || x == b'\r') {
+        line.pop();
+    }
+    let mut fields = line.split(|&x| x == b'\t').map(|x| OsString::from_vec(x.to_vec()));
+    let client_display = fields.next().unwrap_or_default();
+    let args = fields.collect::<Vec<_>>();
+    debug!("daemon: request: {:?}, display: {:?}", args, client_display);
+
+    // The daemon's connection is to its own display so it can't act on another
+    if client_display != env::var_os("DISPLAY").unwrap_or_default() {
+        return writeln!(stream, "{}", MISMATCH);
+    }
+
+    // Catch panics so that a failing command doesn't take down the daemon
+    let (result, output) = capture_stdout(|| {
+        panic::catch_unwind(AssertUnwindSafe(|| execute(app, args)))
+            .unwrap_or_else(|_| Err("command panicked".to_owned()))
+    })?;
+    let response = match result {
+        Ok(()) => "ok".to_owned(),
+        Err(err) => format!("error: {}", err.lines().next().unwrap_or_default().trim_start_matches("error: ")),
+    };
+    writeln!(stream, "{}", response)?;
+    stream.write_all(&output)
+}
+
+/// Run the given function capturing everything it writes to stdout
+/// * Redirects the process's stdout file descriptor so output written by any means is captured
+///
+/// ### Arguments
+/// * `f` - function to run
+fn capture_stdout<T>(f: impl FnOnce() -> T) -> io::Result<(T, Vec<u8>)> {
+    let (mut reader, writer) = io::pipe()?;
+    let stdout = io::stdout();
+    stdout.lock().flush()?;
+    let saved = stdout.as_fd().try_clone_to_owned()?;
+    redirect(writer.as_raw_fd(), stdout.as_raw_fd())?;
+    drop(writer);
+
+    // Drain the pipe while the function runs so that large outputs can't fill it and block
+    let drain = thread::spawn(move || {
+        let mut output = vec![];
+        reader.read_to_end(&mut output).map(|_| output)
+    });
+    let result = f();
+    let _ = stdout.lock().flush();
+    redirect(saved.as_raw_fd(), stdout.as_raw_fd())?;
+    let output = drain.join().map_err(|_| io::Error::other("failed to capture output"))??;
+    Ok((result, output))
+}
+
+/// Point the `to` file descriptor at the file the `from` file descriptor refers to
+///
+/// ### Arguments
+/// * `from` - file descriptor to duplicate
+/// * `to` - file descriptor to replace
+fn redirect(from: RawFd, to: RawFd) -> io::Result<()> {
+    // SAFETY: both file descriptors are open for the duration of the call
+    match unsafe { libc::dup2(from, to) } {
+        -1 => Err(io::Error::last_os_error()),
+        _ => Ok(()),
+    }
+}
+
+/// Parse and execute the given command reconnecting to the X server if the connection was lost
+///
+/// ### Arguments
+/// * `app` - the App definition used to parse the command
+/// * `args` - the command's arguments excluding the binary name
+fn execute(app: &App, args: Vec<OsString>) -> std::result::Result<(), String> {
+    let matches = app
+        .clone()
+        .get_matches_from_safe(std::iter::once(OsStr::new("wmctl").to_owned()).chain(args))
+        .map_err(|x| x.message)?;
+    if matches.is_present("daemon") {
+        return Err("daemon can't be run from the daemon".to_owned());
+    }
+    crate::execute(app, &matches).map_err(|err| {
+        let lost = err.downcast_ref::<libwmctl::ErrorWrapper>().is_some_and(|x| x.is_connection_error());
+        if lost && libwmctl::reconnect().is_ok() {
+            info!("daemon: reconnected to the X server");
+        }
+        err.to_string()
+    })
+}
@@ -1,6 +1,7 @@
 use clap::ArgMatches;
 use libwmctl::prelude::*;
 use prettytable::{format, Cell, Row, Table};
+use witcher::prelude::*;
 
 use crate::utils;
 
@@ -8,14 +9,32 @@ use crate::utils;
 ///
 /// ### Arguments
 /// * `global` - the ArgMatches object for the global arguments
-pub fn run(global: &ArgMatches) {
+pub fn run(global: &ArgMatches) -> Result<()> {
     let matches = global.subcommand_matches("info").unwrap();
 
     if let Some(matches) = matches.subcommand_matches("winmgr") {
         winmgr(matches.is_present("all"));
+    } else if let Some(matches) = matches.subcommand_matches("supported") {
+        if matches.is_present("missing") {
+            missing();
+            return Ok(());
+        }
+        let ids = match matches.value_of("WINDOW") {
+            Some(id) => vec![id.parse::<u32>().pass()?],
+            None => utils::get_window_ids(global, true)?,
+        };
+        for id in ids {
+            supported(id);
+        }
     } else {
-        window(utils::get_window_id(global, true));
+        for (i, id) in utils::get_window_ids(global, true)?.into_iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            window(id);
+        }
     }
+    Ok(())
 }
 
 pub fn winmgr(all: bool) {
@@ -30,6 +49,8 @@ pub fn winmgr(all: bool) {
     println!("Work area:      {}x{}", wm.work_area.0, wm.work_area.1);
     println!("Screen Size:    {}x{}", wm.screen_size.0, wm.screen_size.1);
     println!("Desktops:       {}", wm.desktops);
+    println!("Desktop Geom:   {}x{}", wm.desktop_geometry.0, wm.desktop_geometry.1);
+    println!("Viewport:       {},{}", wm.viewport.0, wm.viewport.1);
     println!("Active Window:  {}", win.id);
     println!();
 
@@ -55,6 +76,35 @@ pub fn winmgr(all: bool) {
     }
 }
 
+// Print out the EWMH atoms the window manager doesn't support
+pub fn missing() {
+    let wm = info().unwrap();
+    let known = ATOM_NAMES.iter().copied().filter(|x| x.starts_with("_NET_")).collect::<Vec<_>>();
+    println!("Window Manager Unsupported Functions: {}", wm.name);
+    println!("-----------------------------------------------------------------------");
+    for name in wm.unsupported(&known) {
+        println!("{}", name);
+    }
+}
+
+// Print out the names of the properties the window defines
+pub fn supported(id: u32) {
+    let win = libwmctl::window(id);
+    println!("Window Supported Properties: {}", win.id);
+    println!("-----------------------------------------------------------------------");
+    for name in win.property_names().unwrap_or_default() {
+        println!("{}", name);
+    }
+}
+
+// Print out the properties the window defines with their values similar to xprop
+pub fn props(id: u32) -> Result<()> {
+    for prop in libwmctl::window(id).properties().pass()? {
+        println!("{} = {}", prop.name, prop.value);
+    }
+    Ok(())
+}
+
 // Print out the window's properties
 pub fn window(id: u32) {
     let wm = info().unwrap();
@@ -62,8 +112,9 @@ pub fn window(id: u32) {
     let parent = win.parent().unwrap();
 
     let (px, py, pw, ph) = parent.visual_geometry().unwrap();
-    let (x, y, w, h) = win.geometry().unwrap();
-    let (vx, vy, vw, vh) = win.visual_geometry().unwrap();
+    let (raw, visual) = win.geometries().unwrap();
+    let (x, y, w, h) = raw.into();
+    let (vx, vy, vw, vh) = visual.into();
     let b = win.borders();
     let g = win.gtk_borders();
 
@@ -71,6 +122,7 @@ pub fn window(id: u32) {
     println!("-----------------------------------------------------------------------");
     println!("Class:        {}", win.class().unwrap_or("".to_owned()));
     println!("Name:         {}", win.name().unwrap_or("".to_owned()));
+    println!("Icon Name:    {}", win.icon_name().unwrap_or("".to_owned()));
     println!("PID:          {}", win.pid().unwrap_or(-1));
     println!("ID:           {}", win.id);
     println!("Parent:       {}", parent.id);
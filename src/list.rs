@@ -9,18 +9,52 @@ use witcher::prelude::*;
 /// * `global` - the ArgMatches object for the global arguments
 pub fn run(global: &ArgMatches) -> Result<()> {
     let matches = global.subcommand_matches("list").unwrap();
-    windows(matches.is_present("all"))
+    if matches.is_present("grouped") {
+        return grouped();
+    }
+    windows(matches)
+}
+
+// List the windows grouped by class
+pub fn grouped() -> Result<()> {
+    let mut table = Table::new();
+    table.set_format(format::FormatBuilder::new().padding(1, 1).build());
+    table.set_titles(Row::new(vec![Cell::new("CLASS"), Cell::new("COUNT"), Cell::new("ID"), Cell::new("NAME")]));
+    for (class, windows) in libwmctl::windows_grouped().pass()? {
+        for (i, win) in windows.iter().enumerate() {
+            let (class, count) =
+                if i == 0 { (class.clone(), windows.len().to_string()) } else { Default::default() };
+            table.add_row(Row::new(vec![
+                Cell::new(&class),
+                Cell::new(&count),
+                Cell::new(&win.id.to_string()),
+                Cell::new(&win.name().unwrap_or("".to_owned())),
+            ]));
+        }
+    }
+    table.printstd();
+
+    Ok(())
 }
 
-// List all windows
-pub fn windows(all: bool) -> Result<()> {
-    let windows = libwmctl::windows(all).unwrap();
+// List all windows according to the list flags
+pub fn windows(matches: &ArgMatches) -> Result<()> {
+    let percent = matches.is_present("percent");
+    let opacity = matches.is_present("opacity");
+    let verbose = matches.is_present("verbose");
+    let names = if matches.is_present("desktop-names") { Some(libwmctl::desktop_names().pass()?) } else { None };
+    let windows = match matches.value_of("monitor") {
+        Some(name) => libwmctl::windows_on_monitor(name).pass()?,
+        None => libwmctl::windows(matches.is_present("all")).unwrap(),
+    };
     let mut table = Table::new();
     table.set_format(format::FormatBuilder::new().padding(1, 1).build());
 
-    table.set_titles(Row::new(vec![
-        Cell::new("ID"),
-        Cell::new("DSK"),
+    let mut titles = vec![Cell::new("ID"), Cell::new("DSK")];
+    if names.is_some() {
+        titles.push(Cell::new("DESKTOP"));
+    }
+    titles.extend(vec![
         Cell::new("PID"),
         Cell::new("X"),
         Cell::new("Y"),
@@ -30,30 +64,80 @@ pub fn windows(all: bool) -> Result<()> {
         Cell::new("PARENT"),
         Cell::new("TYPE"),
         Cell::new("STATE"),
-        Cell::new("CLASS"),
-        Cell::new("NAME"),
-    ]));
+    ]);
+    if opacity {
+        titles.push(Cell::new("OPACITY"));
+    }
+    if verbose {
+        titles.extend(vec![Cell::new("XCLASS"), Cell::new("OR"), Cell::new("MAP")]);
+    }
+    titles.extend(vec![Cell::new("CLASS"), Cell::new("NAME")]);
+    table.set_titles(Row::new(titles));
 
     for win in windows.iter() {
+        // Skip windows that have been destroyed since being listed
+        let attrs = if verbose {
+            match (win.window_class_type(), win.is_override_redirect(), win.mapped()) {
+                (Ok(class), Ok(redirect), Ok(map)) => Some((class, redirect, map)),
+                _ => continue,
+            }
+        } else {
+            None
+        };
         let (x, y, w, h) = win.visual_geometry().unwrap();
+        let (x, y, w, h) = if percent {
+            let a = win.work_area().unwrap();
+            (
+                percentage(x - a.x, a.w),
+                percentage(y - a.y, a.h),
+                percentage(w as i32, a.w),
+                percentage(h as i32, a.h),
+            )
+        } else {
+            (x.to_string(), y.to_string(), w.to_string(), h.to_string())
+        };
         let b = if win.is_gtk() { win.gtk_borders() } else { win.borders() };
-        table.add_row(Row::new(vec![
+        let mut row = vec![
             Cell::new(&win.id.to_string()),
             Cell::new(&format!("{:>2}", win.desktop().unwrap())),
+        ];
+        if let Some(names) = names.as_ref() {
+            let name = win.desktop_opt().unwrap_or(None).and_then(|x| names.get(x as usize - 1));
+            row.push(Cell::new(name.map_or("", |x| x.as_str())));
+        }
+        row.extend(vec![
             Cell::new(&win.pid().unwrap_or(-1).to_string()),
-            Cell::new(&x.to_string()),
-            Cell::new(&y.to_string()),
-            Cell::new(&w.to_string()),
-            Cell::new(&h.to_string()),
+            Cell::new(&x),
+            Cell::new(&y),
+            Cell::new(&w),
+            Cell::new(&h),
             Cell::new(&format!("L{},R{},T{},B{}", b.l, b.r, b.t, b.b)),
             Cell::new(&format!("{}", win.parent().unwrap().id)),
             Cell::new(&win.kind().unwrap_or(Kind::Invalid).to_string()),
             Cell::new(&format!("{:?}", win.state().unwrap_or(vec![]))),
+        ]);
+        if opacity {
+            row.push(Cell::new(&format!("{:.0}%", win.opacity().unwrap_or(1.0) * 100.0)));
+        }
+        if let Some((class, redirect, map)) = attrs {
+            row.extend(vec![
+                Cell::new(&class.to_string()),
+                Cell::new(&redirect.to_string()),
+                Cell::new(&map.to_string()),
+            ]);
+        }
+        row.extend(vec![
             Cell::new(&win.class().unwrap_or("".to_owned())),
             Cell::new(&win.name().unwrap_or("".to_owned())),
-        ]));
+        ]);
+        table.add_row(Row::new(row));
     }
     table.printstd();
 
     Ok(())
 }
+
+// Format the value as a percentage of the given total
+fn percentage(value: i32, total: u32) -> String {
+    format!("{:.1}%", value as f32 * 100.0 / total.max(1) as f32)
+}
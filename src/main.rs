@@ -27,12 +27,13 @@
 //! ```
 use std::env;
 
-use clap::{App, AppSettings, Arg, SubCommand};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use gory::*;
-use tracing::Level;
+use tracing::{warn, Level};
 use tracing_subscriber;
 use witcher::prelude::*;
 
+mod daemon;
 mod info;
 mod list;
 mod place;
@@ -40,7 +41,7 @@ mod utils;
 
 // Configure logging
 #[doc(hidden)]
-fn init_logging(level: Option<Level>) {
+fn init_logging(level: Option<Level>, daemon: bool) {
     // Use the given log level as highest priority
     // Use environment log level as second priority
     // Fallback on INFO if neither is set
@@ -51,41 +52,158 @@ fn init_logging(level: Option<Level>) {
             Err(_e) => Level::INFO, // default to Info
         },
     };
-    tracing_subscriber::fmt()
+    let subscriber = tracing_subscriber::fmt()
         .with_target(false) // turn off file name
-        .with_max_level(loglevel) // set max level to log
         //.json() // uncomment this line to convert it into json output
-        .init();
+        .with_max_level(loglevel); // set max level to log
+
+    // The daemon captures stdout as the output of the commands it runs so it logs to stderr
+    match daemon {
+        true => subscriber.with_writer(std::io::stderr).init(),
+        false => subscriber.init(),
+    }
 }
 
+const APP_NAME: &str = "wmctl";
+const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+const APP_DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
+const APP_GIT_COMMIT: &str = env!("APP_GIT_COMMIT");
+const APP_BUILD_DATE: &str = env!("APP_BUILD_DATE");
+
 #[doc(hidden)]
 fn init() -> Result<()> {
-    const APP_NAME: &str = "wmctl";
-    const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
-    const APP_DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
-    const APP_GIT_COMMIT: &str = env!("APP_GIT_COMMIT");
-    const APP_BUILD_DATE: &str = env!("APP_BUILD_DATE");
-
     // Parse cli args
     // -----------------------------------------------------------------------------------------
-    let matches = App::new(format!("{}", APP_NAME.cyan()))
-        .version(&format!("v{}", APP_VERSION)[..])
-        .about(&format!("{}", APP_DESCRIPTION.green())[..])
+    let version = format!("v{}", APP_VERSION);
+    let about = format!("{}", APP_DESCRIPTION.green());
+    let app = App::new(format!("{}", APP_NAME.cyan()))
+        .version(&version[..])
+        .about(&about[..])
         .setting(AppSettings::SubcommandRequiredElseHelp)
 
         // Global flags
         .arg(Arg::with_name("test").short("t").long("test").takes_value(false).help("Enable test mode"))
         .arg(Arg::with_name("debug").short("d").long("debug").takes_value(false).help("Enable debug logging"))
         .arg(Arg::with_name("quiet").short("q").long("quiet").takes_value(false).help("Disable all logging"))
+        .arg(Arg::with_name("fuzzy").long("fuzzy").takes_value(false).help("Match the closest class when using --class"))
+        .arg(Arg::with_name("all-matches").long("all-matches").takes_value(false).help("Operate on all matching windows not just the first"))
 
         // Global options
         .arg(Arg::with_name("loglevel").long("log-level").value_name("NAME").takes_value(true).help("Sets the log level [error|warn|info|debug|trace] [default: info]"))
         .arg(Arg::with_name("window").short("w").long("window").value_name("WINDOW").takes_value(true).help("Window to operate against"))
-        .arg(Arg::with_name("class").short("c").long("class").value_name("CLASS").takes_value(true).help("Class of window to operate against (first matching)"))
+        .arg(Arg::with_name("class").short("c").long("class").value_name("CLASS").takes_value(true).help("Class of window to operate against (first matching unless --all-matches)"))
+        .arg(Arg::with_name("name").short("n").long("name").value_name("NAME").takes_value(true).help("Name of window to operate against by substring (first matching unless --all-matches)"))
+        .arg(Arg::with_name("at").long("at").value_name("X,Y").takes_value(true).help("Window at the given screen coordinates to operate against"))
 
         // Version command
         .subcommand(SubCommand::with_name("version").alias("v").alias("ver").about("Print version information"))
 
+        // Cycle
+        .subcommand(SubCommand::with_name("cycle").about("Activate the next window in most recently used order")
+            .long_about(r"Activate the next window in most recently used order i.e. Alt-Tab
+
+The history is kept on the root window as _WMCTL_HISTORY so that it carries across invocations.
+It only covers windows wmctl has seen active i.e. the active window each time cycle is run and
+every active window change while the daemon is running. Other windows follow in stacking order.
+
+Examples:
+
+# Activate the previously used window
+wmctl cycle
+
+# Activate the least recently used window
+wmctl cycle --reverse
+")
+            .arg(Arg::with_name("reverse").short("r").long("reverse").takes_value(false).help("Cycle in reverse order"))
+        )
+
+        // Daemon
+        .subcommand(SubCommand::with_name("daemon").about("Run commands over a persistent connection")
+            .long_about(r"Run commands over a single persistent X connection accepting them on a Unix socket
+
+The socket is created at $XDG_RUNTIME_DIR/wmctl.sock falling back on /tmp/wmctl-$USER.sock.
+Each request is a single line of the client's DISPLAY followed by the command's arguments
+separated by tabs and is answered with a single line of either 'ok' or 'error: <message>'
+followed by the command's output, or 'mismatch' if the DISPLAY isn't the daemon's. Normal
+invocations are forwarded to the daemon when it is running on the same display and print the
+output it sends back. Clients must send their request within 2 seconds. The daemon also records active
+window changes in the history used by 'wmctl cycle'.
+
+Examples:
+
+# Run the daemon
+wmctl daemon
+
+# Grow the active window via the daemon
+printf '%s\tshape\tgrow\n' $DISPLAY | socat - UNIX-CONNECT:$XDG_RUNTIME_DIR/wmctl.sock
+")
+        )
+
+        // Decorate
+        .subcommand(SubCommand::with_name("decorate").about("Show the window manager decorations")
+            .long_about(r"Show the window manager's titlebar and border decorations via the Motif hints
+
+Examples:
+
+# Show the decorations of the active window
+wmctl decorate
+")
+        )
+
+        // Desktops
+        .subcommand(SubCommand::with_name("desktops").about("Get or set the number of desktops")
+            .long_about(r"Get or set the number of desktops
+
+Examples:
+
+# Print out the number of desktops
+wmctl desktops
+
+# Set the number of desktops to 4
+wmctl desktops 4
+")
+            .arg(Arg::with_name("COUNT").index(1).required(false).help("number of desktops to have"))
+        )
+
+        // Focus
+        .subcommand(SubCommand::with_name("focus").about("Focus the nearest window in the given direction")
+            .long_about(r"Activate the nearest window in the given direction from the window
+
+Examples:
+
+# Focus the window to the right of the active window
+wmctl focus right
+")
+            .arg(Arg::with_name("DIRECTION").index(1).required(true)
+                .possible_values(&["left", "right", "top", "bottom"])
+                .help("direction to look for the next window in"))
+        )
+
+        // Geom
+        .subcommand(SubCommand::with_name("geom").about("Resize and move the window using pixels or percentages")
+            .long_about(r"Resize and move the window using pixels or percentages of the work area
+
+Values are given as N or Npx for pixels or N% for a percentage of the work area's width for
+WIDTH and X or height for HEIGHT and Y. Pixels for X and Y are screen coordinates while
+percentages are offsets from the work area's top left.
+
+Examples:
+
+# Size the window to half the width and 80% of the height of the work area
+wmctl geom 50% 80%
+
+# Size the window as above and offset it 10% from the left and 5% from the top
+wmctl geom 50% 80% 10% 5%
+
+# Mix units to size the window to 800 pixels wide at 20 pixels from the screen's left
+wmctl geom 800px 100% 20px 0%
+")
+            .arg(Arg::with_name("WIDTH").index(1).required(true).help("width of the window"))
+            .arg(Arg::with_name("HEIGHT").index(2).required(true).help("height of the window"))
+            .arg(Arg::with_name("X").index(3).required(false).requires("Y").help("x location of the window"))
+            .arg(Arg::with_name("Y").index(4).required(false).help("y location of the window"))
+        )
+
         // Info
         .subcommand(SubCommand::with_name("info").about("Print X11 component information")
             .long_about(r"Print out X11 component information e.g. Window Manager, Window or other
@@ -98,10 +216,25 @@ wmctl info
 # Print out information for the first window by class
 wmctl -c firefox info
 
+# Print out information for all windows by class
+wmctl -c firefox --all-matches info
+
+# Print out information for the first window whose name contains the given text
+wmctl -n 'Mozilla Firefox' info
+
 # Print out Window Manager information
 wmctl info winmgr
+
+# Print out the properties the active window defines
+wmctl info supported
+
+# Print out the EWMH atoms the Window Manager doesn't support
+wmctl info supported --missing
 ").subcommand(SubCommand::with_name("winmgr").about("Print out information for the Window Manager")
-    .arg(Arg::with_name("all").long("all").short("a").takes_value(false).help("Show supported Window Manager functions"))))
+    .arg(Arg::with_name("all").long("all").short("a").takes_value(false).help("Show supported Window Manager functions")))
+  .subcommand(SubCommand::with_name("supported").about("Print out the properties a window defines")
+    .arg(Arg::with_name("missing").long("missing").short("m").takes_value(false).help("Show EWMH atoms the Window Manager doesn't support"))
+    .arg(Arg::with_name("WINDOW").index(1).required(false).help("Window to operate against [default: active]"))))
 
         // List out all the windows
         .subcommand(SubCommand::with_name("list").about("List out windows")
@@ -114,8 +247,32 @@ wmctl list
 
 # List out all X windows
 wmctl list -a
+
+# List out windows with geometry as percentages of the work area
+wmctl list --percent
+
+# List out windows with the names of their desktops
+wmctl list --desktop-names
+
+# List out windows with their opacity
+wmctl list --opacity
+
+# List out all X windows with their X class, override redirect flag and map state
+wmctl list -a -v
+
+# List out windows grouped by class
+wmctl list --grouped
+
+# List out windows on the HDMI-1 monitor
+wmctl list --monitor HDMI-1
 ")
         .arg(Arg::with_name("all").short("a").long("all").takes_value(false).help("Show all X windows not just WM windows"))
+        .arg(Arg::with_name("percent").short("p").long("percent").takes_value(false).help("Show geometry as percentages of the work area"))
+        .arg(Arg::with_name("desktop-names").long("desktop-names").takes_value(false).help("Show desktop names"))
+        .arg(Arg::with_name("opacity").long("opacity").takes_value(false).help("Show window opacity"))
+        .arg(Arg::with_name("grouped").short("g").long("grouped").takes_value(false).help("Group windows by class"))
+        .arg(Arg::with_name("monitor").short("m").long("monitor").value_name("NAME").takes_value(true).help("Show only windows on the given monitor"))
+        .arg(Arg::with_name("verbose").short("v").long("verbose").takes_value(false).help("Show X class, override redirect flag and map state"))
         )
 
         // Move
@@ -138,6 +295,22 @@ wmctl move bottom-center
                 .help("position to move the active window to"))
         )
 
+        // Pack
+        .subcommand(SubCommand::with_name("pack").about("Pack windows to remove the gaps between them")
+            .long_about(r"Move windows toward the top left of their monitor's work area removing the gaps
+between them while keeping their sizes unchanged
+
+Examples:
+
+# Pack all visible windows
+wmctl pack
+
+# Pack all visible windows except conky and plank
+wmctl pack -e conky -e plank
+")
+            .arg(Arg::with_name("exclude").short("e").long("exclude").value_name("CLASS").takes_value(true).multiple(true).number_of_values(1).help("Class of windows to leave alone"))
+        )
+
         // Place
         .subcommand(SubCommand::with_name("place").about("Shape and move the window")
             .long_about(r"Shape and move the window
@@ -158,6 +331,36 @@ wmctl place small bottom-left
                 .help("position to move the window to"))
         )
 
+        // Props
+        .subcommand(SubCommand::with_name("props").about("Print out the window's properties with their values")
+            .long_about(r"Print out every property the window defines with its decoded value similar to xprop
+
+Examples:
+
+# Print out the properties of the active window
+wmctl props
+
+# Print out the properties of the given window
+wmctl props 12345
+
+# Print out the properties of the first window by class
+wmctl -c firefox props
+")
+            .arg(Arg::with_name("WINDOW").index(1).required(false).help("Window to operate against [default: active]"))
+        )
+
+        // Rescue
+        .subcommand(SubCommand::with_name("rescue").about("Move off screen windows back on screen")
+            .long_about(r"Move windows that are mostly off screen back on screen e.g. after a resolution
+change or unplugging a monitor
+
+Examples:
+
+# Move all off screen windows back on screen
+wmctl rescue
+")
+        )
+
         // Shape
         .subcommand(SubCommand::with_name("shape").about("Shape the window")
             .long_about(r"Shape the window
@@ -181,10 +384,56 @@ wmctl shape large
                 .help("shape directive to use against the window"))
         )
 
+        // Show desktop
+        .subcommand(SubCommand::with_name("show-desktop").about("Toggle showing the desktop")
+            .long_about(r"Hide all windows to show the desktop or restore them again
+
+Examples:
+
+# Toggle between showing the desktop and showing the windows
+wmctl show-desktop
+
+# Hide all windows to show the desktop
+wmctl show-desktop on
+")
+            .arg(Arg::with_name("STATE").index(1).required(false)
+                .possible_values(&["on", "off", "toggle"])
+                .help("show the desktop, restore the windows or toggle between them [default: toggle]"))
+        )
+
+        // State
+        .subcommand(SubCommand::with_name("state").about("Add, remove or toggle a window state")
+            .long_about(r"Add, remove or toggle a window state by name
+
+States are given by their EWMH name with or without the _NET_WM_STATE_ prefix e.g. fullscreen,
+sticky, above, below, shaded, maximized_horz, maximized_vert, skip_taskbar, skip_pager or
+demands_attention. States the window manager doesn't support are reported as errors.
+
+Examples:
+
+# Toggle fullscreen for the active window
+wmctl state toggle fullscreen
+
+# Keep the firefox window above other windows
+wmctl -c firefox state add above
+
+# Show the active window in the taskbar again
+wmctl state remove skip_taskbar
+")
+            .arg(Arg::with_name("ACTION").index(1).required(true)
+                .possible_values(&["add", "remove", "toggle"])
+                .help("action to apply to the state"))
+            .arg(Arg::with_name("NAME").index(2).required(true).help("name of the state"))
+        )
+
         // Static
         .subcommand(SubCommand::with_name("static").about("Resize and move the window")
             .long_about(r"Resize and move the window statically
 
+Values are given as N or Npx for pixels or N% for a percentage of the work area's width for
+WIDTH and X or height for HEIGHT and Y. Pixels for X and Y are screen coordinates, or relative
+to the monitor's top left if given, while percentages are offsets from the work area's top left.
+
 Examples:
 
 # w and h are static values of the size of the window
@@ -192,21 +441,57 @@ wmctl static 1276 757
 
 # w and h are static values of the size of the window and x, y are the intended location
 wmctl static 1276 757 0 0
+
+# x, y are relative to the top left of the HDMI-1 monitor rather than the screen
+wmctl static 1276 757 0 0 --monitor HDMI-1
+
+# Size the window to half the work area's width and 757 pixels high offset 25% from the left
+wmctl static 50% 757px 25% 0
 ")
             .arg(Arg::with_name("WIDTH").index(1).required(true).help("width of the window"))
             .arg(Arg::with_name("HEIGHT").index(2).required(true).help("height of the window"))
-            .arg(Arg::with_name("X").index(3).required(false).help("x location of the window"))
+            .arg(Arg::with_name("X").index(3).required(false).requires("Y").help("x location of the window"))
             .arg(Arg::with_name("Y").index(4).required(false).help("y location of the window"))
+            .arg(Arg::with_name("monitor").short("m").long("monitor").value_name("NAME").takes_value(true).help("Monitor the x, y location and percentages are relative to"))
         )
-        .get_matches_from_safe(env::args_os()).pass()?;
+
+        // Undecorate
+        .subcommand(SubCommand::with_name("undecorate").about("Hide the window manager decorations")
+            .long_about(r"Hide the window manager's titlebar and border decorations via the Motif hints
+
+Examples:
+
+# Hide the decorations of the active window
+wmctl undecorate
+")
+        );
+    let matches = app.clone().get_matches_from_safe(env::args_os()).pass()?;
 
     // Execute
     // ---------------------------------------------------------------------------------------------
-    init_logging(match matches.is_present("debug") {
-        true => Some(Level::DEBUG),
-        _ => None,
-    });
+    init_logging(
+        match matches.is_present("debug") {
+            true => Some(Level::DEBUG),
+            _ => None,
+        },
+        matches.is_present("daemon"),
+    );
+
+    // Forward commands to the daemon if it is running which sends back their output
+    if !matches.is_present("daemon") {
+        if let Some(result) = daemon::forward(&env::args_os().skip(1).collect::<Vec<_>>()) {
+            return result;
+        }
+    }
+    execute(&app, &matches)
+}
 
+/// Execute the parsed command
+///
+/// ### Arguments
+/// * `app` - the App definition used to parse commands forwarded to the daemon
+/// * `matches` - the ArgMatches object for the global arguments
+fn execute(app: &App, matches: &ArgMatches) -> Result<()> {
     // Version
     if let Some(ref _matches) = matches.subcommand_matches("version") {
         println!("{}: {}", APP_NAME.cyan(), APP_DESCRIPTION.cyan());
@@ -215,21 +500,102 @@ wmctl static 1276 757 0 0
         println!("{:<w$} {}", "Build Date:", APP_BUILD_DATE, w = 18);
         println!("{:<w$} {}", "Git Commit:", APP_GIT_COMMIT, w = 18);
 
+    // cycle
+    } else if let Some(matches) = matches.subcommand_matches("cycle") {
+        libwmctl::cycle_windows(matches.is_present("reverse")).pass()?;
+
+    // daemon
+    } else if matches.is_present("daemon") {
+        daemon::run(app)?;
+
+    // decorate/undecorate
+    } else if matches.is_present("decorate") || matches.is_present("undecorate") {
+        for id in utils::get_window_ids(matches, true)? {
+            libwmctl::window(id).set_decorated(matches.is_present("decorate")).pass()?;
+        }
+
+    // desktops
+    } else if let Some(matches) = matches.subcommand_matches("desktops") {
+        match matches.value_of("COUNT") {
+            Some(count) => libwmctl::set_desktop_count(count.parse::<u32>().pass()?).pass()?,
+            None => println!("{}", libwmctl::desktops().pass()?),
+        }
+
+    // focus
+    } else if let Some(sub) = matches.subcommand_matches("focus") {
+        let direction = libwmctl::Position::try_from(sub.value_of("DIRECTION").unwrap()).pass()?;
+        for id in utils::get_window_ids(matches, true)? {
+            if let Some(win) = libwmctl::window(id).neighbor(direction.clone()).pass()? {
+                win.activate().pass()?;
+            }
+        }
+
     // info
     } else if matches.is_present("info") {
-        info::run(&matches);
+        info::run(matches)?;
 
     // list
     } else if matches.is_present("list") {
-        list::run(&matches)?;
+        list::run(matches)?;
+
+    // pack
+    } else if let Some(matches) = matches.subcommand_matches("pack") {
+        let exclude = matches.values_of("exclude").map(|x| x.collect::<Vec<_>>()).unwrap_or_default();
+        libwmctl::set_ignored_classes(&exclude);
+        libwmctl::pack().pass()?;
+
+    // props
+    } else if let Some(sub) = matches.subcommand_matches("props") {
+        let ids = match sub.value_of("WINDOW") {
+            Some(id) => vec![id.parse::<u32>().pass()?],
+            None => utils::get_window_ids(matches, true)?,
+        };
+        for (i, id) in ids.into_iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            info::props(id)?;
+        }
+
+    // rescue
+    } else if matches.is_present("rescue") {
+        // Keep rescuing the remaining windows when one fails e.g. it was closed mid way
+        for win in libwmctl::windows(false).pass()? {
+            if let Err(err) = win.ensure_on_screen() {
+                warn!("rescue: skipping window {}: {}", win.id, err);
+            }
+        }
+
+    // show-desktop
+    } else if let Some(matches) = matches.subcommand_matches("show-desktop") {
+        let enable = match matches.value_of("STATE") {
+            Some("on") => true,
+            Some("off") => false,
+            _ => !libwmctl::showing_desktop().unwrap_or(false),
+        };
+        libwmctl::set_showing_desktop(enable).pass()?;
+
+    // state
+    } else if let Some(sub) = matches.subcommand_matches("state") {
+        let name = sub.value_of("NAME").unwrap();
+        libwmctl::check_state_supported(name).pass()?;
+        for id in utils::get_window_ids(matches, true)? {
+            let win = libwmctl::window(id);
+            match sub.value_of("ACTION").unwrap() {
+                "add" => win.add_state(name).pass()?,
+                "remove" => win.remove_state(name).pass()?,
+                _ => win.toggle(name).pass()?,
+            }
+        }
 
     // place
-    } else if matches.is_present("move")
+    } else if matches.is_present("geom")
+        || matches.is_present("move")
         || matches.is_present("place")
         || matches.is_present("shape")
         || matches.is_present("static")
     {
-        place::run(&matches)?;
+        place::run(matches)?;
     }
 
     Ok(())
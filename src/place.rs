@@ -9,8 +9,18 @@ use crate::utils;
 /// ### Arguments
 /// * `global` - the ArgMatches object for the global arguments
 pub fn run(global: &ArgMatches) -> Result<()> {
-    let id = utils::get_window_id(global, true);
+    for id in utils::get_window_ids(global, true)? {
+        place(global, id)?;
+    }
+    Ok(())
+}
 
+/// Place the given window according to the subcommand
+///
+/// ### Arguments
+/// * `global` - the ArgMatches object for the global arguments
+/// * `id` - the window to operate against
+fn place(global: &ArgMatches, id: u32) -> Result<()> {
     if let Some(matches) = global.subcommand_matches("move") {
         let pos = Position::try_from(matches.value_of("POSITION").unwrap()).pass()?;
         window(id).pos(pos).place().pass()?;
@@ -23,15 +33,11 @@ pub fn run(global: &ArgMatches) -> Result<()> {
 
     // static
     } else if let Some(matches) = global.subcommand_matches("static") {
-        let w = matches.value_of("WIDTH").unwrap().parse::<u32>().pass()?;
-        let h = matches.value_of("HEIGHT").unwrap().parse::<u32>().pass()?;
-        let mut win = window(id).shape(Shape::Static(w, h));
-        if matches.value_of("X").is_some() && matches.value_of("Y").is_some() {
-            let x = matches.value_of("X").unwrap().parse::<i32>().pass()?;
-            let y = matches.value_of("Y").unwrap().parse::<i32>().pass()?;
-            win = win.pos(Position::Static(x, y));
-        }
-        win.place().pass()?;
+        place_static(matches, id, matches.value_of("monitor"))?;
+
+    // geom i.e. static without a monitor
+    } else if let Some(matches) = global.subcommand_matches("geom") {
+        place_static(matches, id, None)?;
 
     // shape
     } else if let Some(matches) = global.subcommand_matches("shape") {
@@ -41,3 +47,135 @@ pub fn run(global: &ArgMatches) -> Result<()> {
 
     Ok(())
 }
+
+/// Resize and move the given window to the static geometry given by the subcommand's WIDTH, HEIGHT
+/// and optional X and Y arguments
+///
+/// ### Arguments
+/// * `matches` - the ArgMatches object for the static or geom subcommand
+/// * `id` - the window to operate against
+/// * `monitor` - name of the monitor pixel positions and percentages are relative to or None for
+///   screen coordinates and the window's current work area
+fn place_static(matches: &ArgMatches, id: u32, monitor: Option<&str>) -> Result<()> {
+    let monitor = match monitor {
+        Some(name) => Some(libwmctl::monitor(name).pass()?),
+        None => None,
+    };
+
+    // Percentages are relative to the monitor's work area falling back on the window's
+    let area = match &monitor {
+        Some(monitor) => monitor.work_area,
+        None => window(id).work_area().pass()?,
+    };
+    let w = Unit::parse(matches.value_of("WIDTH").unwrap())?.resolve_size(area.w)?;
+    let h = Unit::parse(matches.value_of("HEIGHT").unwrap())?.resolve_size(area.h)?;
+    let mut win = window(id).shape(Shape::Static(w, h));
+    if matches.value_of("X").is_some() && matches.value_of("Y").is_some() {
+        let (ox, oy) = monitor.as_ref().map(|x| (x.geometry.x, x.geometry.y)).unwrap_or_default();
+        let x = Unit::parse(matches.value_of("X").unwrap())?.resolve_pos(ox, area.x, area.w);
+        let y = Unit::parse(matches.value_of("Y").unwrap())?.resolve_pos(oy, area.y, area.h);
+        win = win.pos(Position::Static(x, y));
+    }
+    win.place().pass()
+}
+
+/// Unit provides a simple grammar for resolution independent geometry values
+/// * `N` or `Npx` - a number of pixels, positions are screen coordinates or relative to the
+///   monitor's top left when a monitor is given
+/// * `N%` - a percentage in the range 0-100 of the work area's width or height, positions are
+///   relative to the work area's top left
+#[derive(Debug, PartialEq)]
+enum Unit {
+    Px(i32),
+    Percent(f32),
+}
+
+impl Unit {
+    /// Parse the given token into a unit
+    ///
+    /// ### Arguments
+    /// * `token` - the token to parse e.g. `50%` or `200px`
+    fn parse(token: &str) -> Result<Unit> {
+        let invalid = || WmCtlError::InvalidGeometry(format!("{} must be in the form N, Npx or N%", token));
+        if let Some(value) = token.strip_suffix('%') {
+            match value.parse::<f32>() {
+                Ok(x) if (0.0..=100.0).contains(&x) => Ok(Unit::Percent(x)),
+                _ => Err(invalid()).pass(),
+            }
+        } else {
+            token.strip_suffix("px").unwrap_or(token).parse::<i32>().map(Unit::Px).map_err(|_| invalid()).pass()
+        }
+    }
+
+    /// Resolve the unit into pixels
+    ///
+    /// ### Arguments
+    /// * `total` - the work area's width or height percentages are relative to
+    fn resolve(&self, total: u32) -> i32 {
+        match self {
+            Unit::Px(x) => *x,
+            Unit::Percent(x) => (total as f32 * x / 100.0).round() as i32,
+        }
+    }
+
+    /// Resolve the unit into a position in screen coordinates
+    ///
+    /// ### Arguments
+    /// * `origin` - the screen or monitor's top left coordinate pixels are relative to
+    /// * `start` - the work area's top left coordinate percentages are relative to
+    /// * `total` - the work area's width or height percentages are relative to
+    fn resolve_pos(&self, origin: i32, start: i32, total: u32) -> i32 {
+        match self {
+            Unit::Px(x) => origin + x,
+            Unit::Percent(_) => start + self.resolve(total),
+        }
+    }
+
+    /// Resolve the unit into a size in pixels ensuring it is positive
+    ///
+    /// ### Arguments
+    /// * `total` - the work area's width or height percentages are relative to
+    fn resolve_size(&self, total: u32) -> Result<u32> {
+        match self.resolve(total) {
+            x if x > 0 => Ok(x as u32),
+            x => Err(WmCtlError::InvalidGeometry(format!("size must be positive: {}", x))).pass(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_parse() {
+        assert_eq!(Unit::parse("200").unwrap(), Unit::Px(200));
+        assert_eq!(Unit::parse("200px").unwrap(), Unit::Px(200));
+        assert_eq!(Unit::parse("-20px").unwrap(), Unit::Px(-20));
+        assert_eq!(Unit::parse("50%").unwrap(), Unit::Percent(50.0));
+        assert_eq!(Unit::parse("0%").unwrap(), Unit::Percent(0.0));
+        assert_eq!(Unit::parse("100%").unwrap(), Unit::Percent(100.0));
+
+        // Percentages outside 0-100 and garbage are rejected
+        for token in ["101%", "-1%", "NaN%", "inf%", "%", "", "px", "abc", "10pt", "1.5px", "50%%"] {
+            assert!(Unit::parse(token).is_err(), "{}", token);
+        }
+    }
+
+    #[test]
+    fn test_unit_resolve() {
+        assert_eq!(Unit::Px(200).resolve(1920), 200);
+        assert_eq!(Unit::Percent(50.0).resolve(1920), 960);
+        assert_eq!(Unit::Percent(33.3).resolve(1000), 333);
+
+        // Pixels are relative to the origin while percentages are relative to the work area
+        assert_eq!(Unit::Px(10).resolve_pos(1920, 1950, 2500), 1930);
+        assert_eq!(Unit::Percent(10.0).resolve_pos(1920, 1950, 2500), 2200);
+
+        // Sizes must be positive
+        assert_eq!(Unit::Percent(50.0).resolve_size(1000).unwrap(), 500);
+        assert!(Unit::Px(0).resolve_size(1000).is_err());
+        assert!(Unit::Px(-5).resolve_size(1000).is_err());
+        assert!(Unit::Percent(0.0).resolve_size(1000).is_err());
+    }
+}
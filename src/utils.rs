@@ -1,43 +1,116 @@
 use clap::ArgMatches;
+use std::io;
+use witcher::prelude::*;
 
-/// Log the message and exit
+/// Create an error for the given message
 ///
 /// ### Arguments
-/// * `msg` - the message to log
-fn fatal(msg: &str) {
-    println!("{}", msg);
-    std::process::exit(1);
+/// * `msg` - the message to report
+fn err_msg<T>(msg: &str) -> Result<T> {
+    Err(io::Error::other(msg.to_owned())).pass()
 }
 
-/// Get the window id from the various methods
+/// Get the window ids from the various methods. Only the first matching window is returned unless
+/// the `all-matches` flag was given in which case all matching windows are returned.
 ///
 /// ### Arguments
 /// * `matches` - the ArgMatches object to search
 /// * `active` - if true, get the active window if no other method is given
-pub fn get_window_id(matches: &ArgMatches, active: bool) -> u32 {
-    let mut id = if matches.is_present("window") {
-        let id = matches.value_of("window").unwrap().parse::<u32>().ok();
-        if id.is_none() {
-            fatal(&format!("Invalid Window identifier: {}", matches.value_of("window").unwrap()));
+pub fn get_window_ids(matches: &ArgMatches, active: bool) -> Result<Vec<u32>> {
+    let all = matches.is_present("all-matches");
+    let mut ids = if matches.is_present("window") {
+        match matches.value_of("window").unwrap().parse::<u32>() {
+            Ok(id) => vec![id],
+            Err(_) => {
+                return err_msg(&format!("Invalid Window identifier: {}", matches.value_of("window").unwrap()))
+            },
         }
-        id
     } else if matches.is_present("class") {
-        let id = matches.value_of("class").and_then(|x| libwmctl::first_by_class(x).and_then(|x| Some(x.id)));
-        if id.is_none() {
-            fatal(&format!("Not found Window class: {}", matches.value_of("class").unwrap()));
+        let class = matches.value_of("class").unwrap();
+        let mut ids = if matches.is_present("fuzzy") {
+            closest_by_class(class)
+        } else {
+            libwmctl::all_by_class(class).iter().map(|x| x.id).collect::<Vec<_>>()
+        };
+        if ids.is_empty() {
+            return err_msg(&format!("Not found Window class: {}", class));
+        }
+        if !all {
+            ids.truncate(1);
+        }
+        ids
+    } else if matches.is_present("name") {
+        let name = matches.value_of("name").unwrap();
+        let mut ids = libwmctl::all_by_name(name).iter().map(|x| x.id).collect::<Vec<_>>();
+        if ids.is_empty() {
+            return err_msg(&format!("Not found Window name: {}", name));
+        }
+        if !all {
+            ids.truncate(1);
+        }
+        ids
+    } else if matches.is_present("at") {
+        let at = matches.value_of("at").unwrap();
+        let point = at.split_once(',').and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)));
+        let Some((x, y)) = point else {
+            return err_msg(&format!("Invalid point, expected X,Y: {}", at));
+        };
+        match libwmctl::window_at(x, y).pass()? {
+            Some(win) => vec![win.id],
+            None => return err_msg(&format!("Not found Window at: {}", at)),
         }
-        id
     } else {
-        None
+        vec![]
     };
 
     // Use the active window if no other method is given and authorized
-    if id.is_none() {
+    if ids.is_empty() {
         if active {
-            id = Some(libwmctl::active().id);
+            ids.push(libwmctl::active().id);
         } else {
-            fatal("Window identifier was not given");
+            return err_msg("Window identifier was not given");
+        }
+    }
+    Ok(ids)
+}
+
+/// Get the ids of the windows whose class is closest to the given class by edit distance. Windows
+/// are returned in the order of the window manager's client list.
+///
+/// ### Arguments
+/// * `class` - the class to match against
+fn closest_by_class(class: &str) -> Vec<u32> {
+    let class = class.to_lowercase();
+    let mut best: Option<usize> = None;
+    let mut ids = vec![];
+    for win in libwmctl::windows(false).unwrap_or_default().iter() {
+        let score = levenshtein(&class, &win.class().unwrap_or_default().to_lowercase());
+        if best.is_none_or(|x| score < x) {
+            best = Some(score);
+            ids.clear();
+        }
+        if best == Some(score) {
+            ids.push(win.id);
+        }
+    }
+    ids
+}
+
+/// Calculate the Levenshtein edit distance between the two strings
+///
+/// ### Arguments
+/// * `a` - first string to compare
+/// * `b` - second string to compare
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
         }
+        prev = curr;
     }
-    id.unwrap()
+    prev[b.len()]
 }